@@ -1,10 +1,12 @@
 use std::ops::{Deref, DerefMut};
+#[cfg(any(unix, all(target_os = "wasi", not(target_env = "p1"))))]
+use std::os::fd::AsFd;
 #[cfg(any(unix, target_os = "wasi"))]
 use std::os::fd::AsRawFd;
 // TODO: once <https://github.com/rust-lang/rust/issues/126198> is fixed this
 // can use `std::os::fd` and be merged with the above.
 #[cfg(target_os = "hermit")]
-use std::os::hermit::io::AsRawFd;
+use std::os::hermit::io::{AsFd, AsRawFd};
 #[cfg(windows)]
 use std::os::windows::io::AsRawSocket;
 #[cfg(debug_assertions)]
@@ -14,8 +16,8 @@ use std::{fmt, io};
 use crate::sys::IoSourceState;
 use crate::{event, Interest, Registry, Token};
 
-/// Adapter for a [`RawFd`] or [`RawSocket`] providing an [`event::Source`]
-/// implementation.
+/// Adapter for a [`RawFd`] (or `RawSocket` on Windows) providing an
+/// [`event::Source`] implementation.
 ///
 /// `IoSource` enables registering any FD or socket wrapper with [`Poll`].
 ///
@@ -23,8 +25,13 @@ use crate::{event, Interest, Registry, Token};
 /// Mio supports registering any FD or socket that can be registered with the
 /// underlying OS selector. `IoSource` provides the necessary bridge.
 ///
+/// Compared to `SourceFd` `IoSource` owns the I/O type and keeps the state
+/// some selectors need, e.g. the `poll(2)` based selector needs to re-arm the
+/// FD after an operation returned a [`WouldBlock`] error. In debug mode it also
+/// checks that the source is not registered with multiple [`Registry`]s.
+///
 /// [`RawFd`]: std::os::fd::RawFd
-/// [`RawSocket`]: std::os::windows::io::RawSocket
+/// [`WouldBlock`]: io::ErrorKind::WouldBlock
 ///
 /// # Notes
 ///
@@ -34,6 +41,58 @@ use crate::{event, Interest, Registry, Token};
 ///
 /// [`Poll`]: crate::Poll
 /// [`do_io`]: IoSource::do_io
+///
+/// # Examples
+///
+/// Implementing a custom I/O type backed by a FD.
+///
+#[cfg_attr(all(unix, feature = "os-poll", feature = "os-ext"), doc = "```")]
+#[cfg_attr(
+    not(all(unix, feature = "os-poll", feature = "os-ext")),
+    doc = "```ignore"
+)]
+/// use std::io;
+/// use std::os::unix::net::UnixDatagram;
+///
+/// use mio::unix::IoSource;
+/// use mio::{event, Interest, Registry, Token};
+///
+/// # #[allow(dead_code)]
+/// pub struct MyIo {
+///     inner: IoSource<UnixDatagram>,
+/// }
+///
+/// # #[allow(dead_code)]
+/// impl MyIo {
+///     pub fn new(socket: UnixDatagram) -> io::Result<MyIo> {
+///         socket.set_nonblocking(true)?;
+///         Ok(MyIo { inner: IoSource::new(socket) })
+///     }
+///
+///     pub fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+///         // All I/O must go through `do_io`.
+///         self.inner.do_io(|socket| socket.recv(buf))
+///     }
+/// }
+///
+/// impl event::Source for MyIo {
+///     fn register(&mut self, registry: &Registry, token: Token, interests: Interest)
+///         -> io::Result<()>
+///     {
+///         self.inner.register(registry, token, interests)
+///     }
+///
+///     fn reregister(&mut self, registry: &Registry, token: Token, interests: Interest)
+///         -> io::Result<()>
+///     {
+///         self.inner.reregister(registry, token, interests)
+///     }
+///
+///     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+///         self.inner.deregister(registry)
+///     }
+/// }
+/// ```
 pub struct IoSource<T> {
     state: IoSourceState,
     inner: T,
@@ -111,7 +170,7 @@ impl<T> DerefMut for IoSource<T> {
 ))]
 impl<T> event::Source for IoSource<T>
 where
-    T: AsRawFd,
+    T: AsFd,
 {
     fn register(
         &mut self,
@@ -121,8 +180,18 @@ where
     ) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.associate(registry)?;
-        self.state
-            .register(registry, token, interests, self.inner.as_raw_fd())
+        let fd = self.inner.as_fd().as_raw_fd();
+        if let Err(err) = self.state.register(registry, token, interests, fd) {
+            // Allow registering again.
+            #[cfg(debug_assertions)]
+            let _ = self.selector_id.remove_association(registry);
+            return Err(err);
+        }
+        #[cfg(all(unix, feature = "test-util"))]
+        if let Some(mock) = registry.mock() {
+            mock.register(fd, token, interests);
+        }
+        Ok(())
     }
 
     fn reregister(
@@ -133,14 +202,25 @@ where
     ) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.check_association(registry)?;
-        self.state
-            .reregister(registry, token, interests, self.inner.as_raw_fd())
+        let fd = self.inner.as_fd().as_raw_fd();
+        self.state.reregister(registry, token, interests, fd)?;
+        #[cfg(all(unix, feature = "test-util"))]
+        if let Some(mock) = registry.mock() {
+            mock.reregister(fd, token, interests);
+        }
+        Ok(())
     }
 
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
         #[cfg(debug_assertions)]
         self.selector_id.remove_association(registry)?;
-        self.state.deregister(registry, self.inner.as_raw_fd())
+        let fd = self.inner.as_fd().as_raw_fd();
+        self.state.deregister(registry, fd)?;
+        #[cfg(all(unix, feature = "test-util"))]
+        if let Some(mock) = registry.mock() {
+            mock.deregister(fd);
+        }
+        Ok(())
     }
 }
 
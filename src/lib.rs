@@ -64,6 +64,10 @@ cfg_net! {
     pub mod net;
 }
 
+cfg_test_util! {
+    pub mod test_util;
+}
+
 #[doc(no_inline)]
 pub use event::Events;
 pub use interest::Interest;
@@ -85,7 +89,8 @@ pub mod unix {
         pub use crate::sys::pipe::{new, Receiver, Sender};
     }
 
-    pub use crate::sys::SourceFd;
+    pub use crate::io_source::IoSource;
+    pub use crate::sys::{BorrowedSource, OwnedSource, SourceFd};
 }
 
 #[cfg(all(target_os = "hermit", feature = "os-ext"))]
@@ -93,7 +98,7 @@ pub mod unix {
 pub mod hermit {
     //! Hermit only extensions.
 
-    pub use crate::sys::SourceFd;
+    pub use crate::sys::{BorrowedSource, OwnedSource, SourceFd};
 }
 
 #[cfg(all(target_os = "wasi", not(target_env = "p1"), feature = "os-ext"))]
@@ -101,7 +106,7 @@ pub mod hermit {
 pub mod wasi {
     //! WASI-only extensions.
 
-    pub use crate::sys::SourceFd;
+    pub use crate::sys::{BorrowedSource, OwnedSource, SourceFd};
 }
 
 #[cfg(all(windows, feature = "os-ext"))]
@@ -136,6 +141,12 @@ pub mod features {
     #![cfg_attr(not(feature = "net"), doc = "## Network types (disabled)")]
     //!
     //! The `net` feature enables networking primitives in the `net` module.
+    //!
+    #![cfg_attr(feature = "test-util", doc = "## `test-util` (enabled)")]
+    #![cfg_attr(not(feature = "test-util"), doc = "## `test-util` (disabled)")]
+    //!
+    //! The `test-util` feature enables utilities for testing code using Mio in
+    //! the `test_util` module, e.g. a mock `Poll`. It implies `os-poll`.
 }
 
 pub mod guide {
@@ -46,6 +46,17 @@ macro_rules! cfg_net {
     }
 }
 
+/// The `test-util` feature is enabled.
+macro_rules! cfg_test_util {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "test-util")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+            $item
+        )*
+    }
+}
+
 /// One of the features enabled that needs `IoSource`. That is `net` or `os-ext`
 /// on Unix (for `pipe`).
 macro_rules! cfg_io_source {
@@ -69,6 +80,30 @@ macro_rules! cfg_any_os_ext {
     }
 }
 
+/// The target supports setting the TCP keepalive idle time, interval and
+/// number of retries.
+macro_rules! cfg_tcp_keepalive {
+    ($($item:item)*) => {
+        $(
+            #[cfg(any(
+                target_os = "android",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "illumos",
+                target_os = "ios",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "netbsd",
+                target_os = "solaris",
+                target_os = "tvos",
+                target_os = "visionos",
+                target_os = "watchos",
+            ))]
+            $item
+        )*
+    }
+}
+
 macro_rules! trace {
     ($($t:tt)*) => {
         log!(trace, $($t)*)
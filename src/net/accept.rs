@@ -0,0 +1,151 @@
+use std::fs::File;
+use std::io;
+use std::os::fd::OwnedFd;
+
+/// Reason [`TcpListener::accept_many`] or [`UnixListener::accept_many`]
+/// stopped accepting connections.
+///
+/// [`TcpListener::accept_many`]: crate::net::TcpListener::accept_many
+/// [`UnixListener::accept_many`]: crate::net::UnixListener::accept_many
+#[derive(Debug)]
+pub enum AcceptStop {
+    /// The maximum number of connections was accepted, more connections may be
+    /// ready to be accepted.
+    Limit,
+    /// No more connections are ready to be accepted, wait for another readable
+    /// event before accepting again.
+    WouldBlock,
+    /// Accepting failed because of a lack of resources, e.g. the process
+    /// reached its limit of open file descriptors (`EMFILE`). The connections
+    /// stay in the accept queue, accepting can be retried once resources have
+    /// been freed.
+    Resources(io::Error),
+}
+
+impl AcceptStop {
+    /// Returns true if the status is [`AcceptStop::Limit`].
+    pub const fn is_limit(&self) -> bool {
+        matches!(self, AcceptStop::Limit)
+    }
+
+    /// Returns true if the status is [`AcceptStop::WouldBlock`].
+    pub const fn is_would_block(&self) -> bool {
+        matches!(self, AcceptStop::WouldBlock)
+    }
+
+    /// Returns true if the status is [`AcceptStop::Resources`].
+    pub const fn is_resources(&self) -> bool {
+        matches!(self, AcceptStop::Resources(_))
+    }
+}
+
+/// Call `accept` until `max` connections are added to `connections`, or until
+/// it returns `WouldBlock` or an error caused by a lack of resources.
+///
+/// Connections that are aborted before they're accepted are skipped. Other
+/// errors are returned, connections accepted before the error are kept in
+/// `connections`.
+pub(crate) fn accept_many<T>(
+    connections: &mut Vec<T>,
+    max: usize,
+    mut accept: impl FnMut() -> io::Result<T>,
+) -> io::Result<AcceptStop> {
+    let mut accepted = 0;
+    while accepted < max {
+        match accept() {
+            Ok(connection) => {
+                connections.push(connection);
+                accepted += 1;
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                return Ok(AcceptStop::WouldBlock)
+            }
+            Err(ref err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::Interrupted | io::ErrorKind::ConnectionAborted
+                ) => {}
+            Err(err) if is_resource_error(&err) => return Ok(AcceptStop::Resources(err)),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(AcceptStop::Limit)
+}
+
+fn is_resource_error(err: &io::Error) -> bool {
+    matches!(
+        err.raw_os_error(),
+        Some(libc::EMFILE | libc::ENFILE | libc::ENOBUFS | libc::ENOMEM)
+    )
+}
+
+/// Reserved file descriptor used to accept connections when the process ran
+/// out of file descriptors, see [`TcpListener::accept_reserved`].
+///
+/// When the process reaches its file descriptor limit accepting fails with
+/// `EMFILE` (or `ENFILE` for the system wide limit), leaving the connection in
+/// the accept queue. As the listener is not ready again until a new connection
+/// arrives the connections in the queue are never handled. To prevent this the
+/// reserved file descriptor is closed, the connection is accepted and closed
+/// directly (shed), after which the file descriptor is reserved again.
+///
+/// [`TcpListener::accept_reserved`]: crate::net::TcpListener::accept_reserved
+#[derive(Debug)]
+pub struct AcceptReserve {
+    fd: Option<OwnedFd>,
+}
+
+/// Result of accepting a connection using an [`AcceptReserve`].
+#[derive(Debug)]
+pub enum Accepted<S, A> {
+    /// Accepted a stream, with the address of the peer.
+    Stream(S, A),
+    /// Accepted a connection but closed it directly, as the process ran out of
+    /// file descriptors. Contains the address of the peer.
+    Shed(A),
+}
+
+impl AcceptReserve {
+    /// Create a new `AcceptReserve`, reserving a file descriptor.
+    pub fn new() -> io::Result<AcceptReserve> {
+        reserve_fd().map(|fd| AcceptReserve { fd: Some(fd) })
+    }
+
+    /// Returns true if a file descriptor is reserved.
+    ///
+    /// If the file descriptor couldn't be reserved again after shedding a
+    /// connection, e.g. because another thread opened a file in the meantime,
+    /// reserving is retried on the next accept.
+    pub const fn is_reserved(&self) -> bool {
+        self.fd.is_some()
+    }
+
+    pub(crate) fn accept<S, A>(
+        &mut self,
+        accept: impl Fn() -> io::Result<(S, A)>,
+    ) -> io::Result<Accepted<S, A>> {
+        if self.fd.is_none() {
+            self.fd = reserve_fd().ok();
+        }
+        match accept() {
+            Ok((stream, address)) => Ok(Accepted::Stream(stream, address)),
+            Err(ref err) if is_fd_limit_error(err) && self.fd.is_some() => {
+                self.fd = None;
+                // Close the stream before reserving the file descriptor again.
+                let result = accept().map(|(_, address)| address);
+                self.fd = reserve_fd().ok();
+                result.map(Accepted::Shed)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn reserve_fd() -> io::Result<OwnedFd> {
+    // Opened with `O_CLOEXEC`.
+    File::open("/dev/null").map(OwnedFd::from)
+}
+
+fn is_fd_limit_error(err: &io::Error) -> bool {
+    matches!(err.raw_os_error(), Some(libc::EMFILE | libc::ENFILE))
+}
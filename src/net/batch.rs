@@ -0,0 +1,120 @@
+/// A datagram to send using `send_many`, see [`UdpSocket::send_many`] and
+/// [`UnixDatagram::send_many`].
+///
+/// `A` is the address type of the socket, [`std::net::SocketAddr`] for
+/// [`UdpSocket`] and [`std::os::unix::net::SocketAddr`] for [`UnixDatagram`].
+///
+/// [`UdpSocket::send_many`]: crate::net::UdpSocket::send_many
+/// [`UnixDatagram::send_many`]: crate::net::UnixDatagram::send_many
+/// [`UdpSocket`]: crate::net::UdpSocket
+/// [`UnixDatagram`]: crate::net::UnixDatagram
+#[derive(Clone, Debug)]
+pub struct SendDatagram<'a, A> {
+    buf: &'a [u8],
+    addr: Option<A>,
+}
+
+impl<'a, A> SendDatagram<'a, A> {
+    /// Datagram with the contents of `buf` to send to the address the socket
+    /// is connected to.
+    pub const fn new(buf: &'a [u8]) -> SendDatagram<'a, A> {
+        SendDatagram { buf, addr: None }
+    }
+
+    /// Datagram with the contents of `buf` to send to `addr`.
+    pub const fn to(buf: &'a [u8], addr: A) -> SendDatagram<'a, A> {
+        SendDatagram {
+            buf,
+            addr: Some(addr),
+        }
+    }
+
+    /// Returns the contents of the datagram.
+    pub const fn buf(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// Returns the address to send the datagram to, if any.
+    pub const fn addr(&self) -> Option<&A> {
+        self.addr.as_ref()
+    }
+}
+
+/// A buffer to receive a datagram into using `recv_many`, see
+/// [`UdpSocket::recv_many`] and [`UnixDatagram::recv_many`].
+///
+/// `A` is the address type of the socket, [`std::net::SocketAddr`] for
+/// [`UdpSocket`] and [`std::os::unix::net::SocketAddr`] for [`UnixDatagram`].
+///
+/// [`UdpSocket::recv_many`]: crate::net::UdpSocket::recv_many
+/// [`UnixDatagram::recv_many`]: crate::net::UnixDatagram::recv_many
+/// [`UdpSocket`]: crate::net::UdpSocket
+/// [`UnixDatagram`]: crate::net::UnixDatagram
+#[derive(Debug)]
+pub struct RecvDatagram<'a, A> {
+    buf: &'a mut [u8],
+    len: usize,
+    addr: Option<A>,
+    truncated: bool,
+}
+
+impl<'a, A> RecvDatagram<'a, A> {
+    /// Create a new `RecvDatagram` receiving into `buf`.
+    pub fn new(buf: &'a mut [u8]) -> RecvDatagram<'a, A> {
+        RecvDatagram {
+            buf,
+            len: 0,
+            addr: None,
+            truncated: false,
+        }
+    }
+
+    /// Returns the received data.
+    pub fn data(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Returns the number of bytes received.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no bytes were received, which is also the case for
+    /// empty datagrams.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the address the datagram was received from, `None` if nothing
+    /// was received (yet).
+    pub const fn addr(&self) -> Option<&A> {
+        self.addr.as_ref()
+    }
+
+    /// Returns true if the datagram was larger than the buffer, in which case
+    /// the remainder of the datagram was discarded.
+    pub const fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    #[cfg_attr(not(feature = "os-poll"), allow(dead_code))]
+    pub(crate) fn buf_mut(&mut self) -> &mut [u8] {
+        self.buf
+    }
+
+    /// Marks the datagram as not received, e.g. when reusing it for another
+    /// call.
+    #[cfg_attr(not(feature = "os-poll"), allow(dead_code))]
+    pub(crate) fn reset(&mut self) {
+        self.len = 0;
+        self.addr = None;
+        self.truncated = false;
+    }
+
+    #[cfg_attr(not(feature = "os-poll"), allow(dead_code))]
+    pub(crate) fn set_received(&mut self, len: usize, addr: A, truncated: bool) {
+        self.len = len;
+        self.addr = Some(addr);
+        self.truncated = truncated;
+    }
+}
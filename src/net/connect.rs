@@ -0,0 +1,55 @@
+use std::io;
+
+/// Status of a non-blocking connect, see [`TcpStream::connect_status`].
+///
+/// [`TcpStream::connect_status`]: crate::net::TcpStream::connect_status
+#[derive(Debug)]
+pub enum ConnectStatus {
+    /// The connection is not yet established. Wait for another writable event
+    /// and check again.
+    Pending,
+    /// The connection is established, the stream can be used.
+    Connected,
+    /// The connection failed, e.g. because the peer refused it.
+    Failed(io::Error),
+}
+
+impl ConnectStatus {
+    /// Returns true if the status is [`ConnectStatus::Pending`].
+    pub const fn is_pending(&self) -> bool {
+        matches!(self, ConnectStatus::Pending)
+    }
+
+    /// Returns true if the status is [`ConnectStatus::Connected`].
+    pub const fn is_connected(&self) -> bool {
+        matches!(self, ConnectStatus::Connected)
+    }
+
+    /// Returns true if the status is [`ConnectStatus::Failed`].
+    pub const fn is_failed(&self) -> bool {
+        matches!(self, ConnectStatus::Failed(_))
+    }
+}
+
+/// Determine the status of a non-blocking connect.
+///
+/// `take_error` and `peer_addr` are the methods of the stream. First checks
+/// `SO_ERROR`, which contains the error of a failed connect. If there is no
+/// error the stream is connected if it has a peer address. The connect is
+/// still in progress if that fails with `ENOTCONN` (or `EINPROGRESS` on some
+/// platforms), which is also the case for a spurious writable event.
+pub(crate) fn connect_status<A>(
+    take_error: io::Result<Option<io::Error>>,
+    peer_addr: impl FnOnce() -> io::Result<A>,
+) -> io::Result<ConnectStatus> {
+    if let Some(err) = take_error? {
+        return Ok(ConnectStatus::Failed(err));
+    }
+    match peer_addr() {
+        Ok(_) => Ok(ConnectStatus::Connected),
+        Err(ref err) if err.kind() == io::ErrorKind::NotConnected => Ok(ConnectStatus::Pending),
+        #[cfg(unix)]
+        Err(ref err) if err.raw_os_error() == Some(libc::EINPROGRESS) => Ok(ConnectStatus::Pending),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,349 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::{Duration, SystemTime};
+use std::{fmt, io, mem, ptr};
+
+/// A typed control message (ancillary data) sent or received along with a
+/// datagram, see [`UdpSocket::send_msg`] and [`UdpSocket::recv_msg`].
+///
+/// [`UdpSocket::send_msg`]: crate::net::UdpSocket::send_msg
+/// [`UdpSocket::recv_msg`]: crate::net::UdpSocket::recv_msg
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ControlMessage {
+    /// Packet information (`IP_PKTINFO` or `IPV6_PKTINFO`).
+    ///
+    /// When received this contains the destination address of the datagram
+    /// and the interface it was received on, requires
+    /// [`UdpSocket::set_recv_pktinfo`]. When sent this sets the source address
+    /// and the interface to send the datagram from. An unspecified address or
+    /// interface index of zero lets the OS pick one.
+    ///
+    /// [`UdpSocket::set_recv_pktinfo`]: crate::net::UdpSocket::set_recv_pktinfo
+    PacketInfo {
+        /// Destination address when received, source address when sent.
+        addr: IpAddr,
+        /// Index of the network interface.
+        interface: u32,
+    },
+    /// IPv4 time-to-live (`IP_TTL`).
+    ///
+    /// When received this is the TTL of the datagram, requires
+    /// [`UdpSocket::set_recv_ttl`]. When sent this overwrites the TTL of the
+    /// socket for this datagram.
+    ///
+    /// [`UdpSocket::set_recv_ttl`]: crate::net::UdpSocket::set_recv_ttl
+    Ttl(u8),
+    /// IPv6 hop limit (`IPV6_HOPLIMIT`), the IPv6 equivalent of [`Ttl`].
+    ///
+    /// [`Ttl`]: ControlMessage::Ttl
+    HopLimit(u8),
+    /// Time at which the datagram was received by the kernel
+    /// (`SO_TIMESTAMPNS`), requires [`UdpSocket::set_recv_timestamp`]. Can't
+    /// be sent.
+    ///
+    /// [`UdpSocket::set_recv_timestamp`]: crate::net::UdpSocket::set_recv_timestamp
+    Timestamp(SystemTime),
+    /// Number of datagrams dropped by the socket, e.g. because its receive
+    /// buffer was full, before this datagram was received (`SO_RXQ_OVFL`),
+    /// requires [`UdpSocket::set_recv_overflow`]. Only received if non-zero.
+    /// Can't be sent.
+    ///
+    /// [`UdpSocket::set_recv_overflow`]: crate::net::UdpSocket::set_recv_overflow
+    Overflow(u32),
+}
+
+impl ControlMessage {
+    /// Parse the data of a control message, returns `None` for unknown
+    /// messages.
+    fn parse(level: libc::c_int, kind: libc::c_int, data: &[u8]) -> Option<ControlMessage> {
+        // SAFETY: all types read below are valid for all bit patterns.
+        unsafe {
+            match (level, kind) {
+                (libc::IPPROTO_IP, libc::IP_PKTINFO) => {
+                    let info: libc::in_pktinfo = read(data)?;
+                    Some(ControlMessage::PacketInfo {
+                        addr: IpAddr::V4(Ipv4Addr::from(info.ipi_addr.s_addr.to_ne_bytes())),
+                        interface: info.ipi_ifindex as u32,
+                    })
+                }
+                (libc::IPPROTO_IPV6, libc::IPV6_PKTINFO) => {
+                    let info: libc::in6_pktinfo = read(data)?;
+                    Some(ControlMessage::PacketInfo {
+                        addr: IpAddr::V6(Ipv6Addr::from(info.ipi6_addr.s6_addr)),
+                        // `c_int` on Android.
+                        #[allow(clippy::unnecessary_cast)]
+                        interface: info.ipi6_ifindex as u32,
+                    })
+                }
+                (libc::IPPROTO_IP, libc::IP_TTL) => {
+                    Some(ControlMessage::Ttl(read::<libc::c_int>(data)? as u8))
+                }
+                (libc::IPPROTO_IPV6, libc::IPV6_HOPLIMIT) => {
+                    Some(ControlMessage::HopLimit(read::<libc::c_int>(data)? as u8))
+                }
+                (libc::SOL_SOCKET, libc::SCM_TIMESTAMPNS) => {
+                    let ts: libc::timespec = read(data)?;
+                    let since_epoch = Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32);
+                    Some(ControlMessage::Timestamp(
+                        SystemTime::UNIX_EPOCH + since_epoch,
+                    ))
+                }
+                (libc::SOL_SOCKET, libc::SO_RXQ_OVFL) => {
+                    Some(ControlMessage::Overflow(read::<u32>(data)?))
+                }
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Read a `T` from the start of `data`, returns `None` if it's too small.
+///
+/// # Safety
+///
+/// All bit patterns must be valid for `T`.
+unsafe fn read<T>(data: &[u8]) -> Option<T> {
+    if data.len() < mem::size_of::<T>() {
+        return None;
+    }
+    Some(ptr::read_unaligned(data.as_ptr().cast()))
+}
+
+/// Control message buffer, aligned for `cmsghdr`.
+#[derive(Copy, Clone)]
+#[repr(C, align(8))]
+struct Buf<const N: usize>([u8; N]);
+
+/// Builder for the control messages to send along with a datagram, see
+/// [`UdpSocket::send_msg`].
+///
+/// [`UdpSocket::send_msg`]: crate::net::UdpSocket::send_msg
+///
+/// # Examples
+///
+/// ```
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// use mio::net::{ControlMessage, SendControl};
+///
+/// let mut control = SendControl::new();
+/// control.push(ControlMessage::PacketInfo {
+///     addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+///     interface: 0,
+/// })?;
+/// control.push(ControlMessage::Ttl(16))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct SendControl {
+    buf: Buf<128>,
+    len: usize,
+}
+
+impl SendControl {
+    /// Create an empty `SendControl`.
+    pub const fn new() -> SendControl {
+        SendControl {
+            buf: Buf([0; 128]),
+            len: 0,
+        }
+    }
+
+    /// Add `message` to the control messages.
+    ///
+    /// Returns an `InvalidInput` error if `message` can't be sent, or if
+    /// there is no more space for it.
+    pub fn push(&mut self, message: ControlMessage) -> io::Result<()> {
+        match message {
+            ControlMessage::PacketInfo {
+                addr: IpAddr::V4(addr),
+                interface,
+            } => {
+                // SAFETY: all zeroes is a valid `in_pktinfo`.
+                let mut info: libc::in_pktinfo = unsafe { mem::zeroed() };
+                info.ipi_ifindex = interface as libc::c_int;
+                info.ipi_spec_dst.s_addr = u32::from_ne_bytes(addr.octets());
+                self.push_raw(libc::IPPROTO_IP, libc::IP_PKTINFO, info)
+            }
+            ControlMessage::PacketInfo {
+                addr: IpAddr::V6(addr),
+                interface,
+            } => {
+                // SAFETY: all zeroes is a valid `in6_pktinfo`.
+                let mut info: libc::in6_pktinfo = unsafe { mem::zeroed() };
+                info.ipi6_ifindex = interface as _;
+                info.ipi6_addr.s6_addr = addr.octets();
+                self.push_raw(libc::IPPROTO_IPV6, libc::IPV6_PKTINFO, info)
+            }
+            ControlMessage::Ttl(ttl) => {
+                self.push_raw(libc::IPPROTO_IP, libc::IP_TTL, libc::c_int::from(ttl))
+            }
+            ControlMessage::HopLimit(hop_limit) => self.push_raw(
+                libc::IPPROTO_IPV6,
+                libc::IPV6_HOPLIMIT,
+                libc::c_int::from(hop_limit),
+            ),
+            ControlMessage::Timestamp(_) | ControlMessage::Overflow(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "control message can't be sent",
+            )),
+        }
+    }
+
+    fn push_raw<T>(&mut self, level: libc::c_int, kind: libc::c_int, data: T) -> io::Result<()> {
+        // SAFETY: `CMSG_SPACE` and `CMSG_LEN` are safe to call.
+        let (space, len, header_len) = unsafe {
+            (
+                libc::CMSG_SPACE(mem::size_of::<T>() as _) as usize,
+                libc::CMSG_LEN(mem::size_of::<T>() as _) as usize,
+                libc::CMSG_LEN(0) as usize,
+            )
+        };
+        if self.buf.0.len() - self.len < space {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no space left for control message",
+            ));
+        }
+        // SAFETY: all zeroes is a valid `cmsghdr`.
+        let mut header: libc::cmsghdr = unsafe { mem::zeroed() };
+        header.cmsg_len = len as _;
+        header.cmsg_level = level;
+        header.cmsg_type = kind;
+        let msg = &mut self.buf.0[self.len..self.len + space];
+        msg.fill(0);
+        // SAFETY: checked above that `msg` is large enough for the header and
+        // data.
+        unsafe {
+            ptr::write_unaligned(msg.as_mut_ptr().cast(), header);
+            ptr::write_unaligned(msg[header_len..].as_mut_ptr().cast(), data);
+        }
+        self.len += space;
+        Ok(())
+    }
+
+    /// Remove all control messages.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Returns true if no control messages were added.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[cfg_attr(not(feature = "os-poll"), allow(dead_code))]
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.buf.0[..self.len]
+    }
+}
+
+impl Default for SendControl {
+    fn default() -> SendControl {
+        SendControl::new()
+    }
+}
+
+impl fmt::Debug for SendControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendControl")
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// Buffer for the control messages received along with a datagram, see
+/// [`UdpSocket::recv_msg`].
+///
+/// Which control messages are received depends on the options set on the
+/// socket, e.g. [`UdpSocket::set_recv_pktinfo`].
+///
+/// [`UdpSocket::recv_msg`]: crate::net::UdpSocket::recv_msg
+/// [`UdpSocket::set_recv_pktinfo`]: crate::net::UdpSocket::set_recv_pktinfo
+#[derive(Clone)]
+pub struct RecvControl {
+    buf: Buf<256>,
+    len: usize,
+    truncated: bool,
+}
+
+impl RecvControl {
+    /// Create an empty `RecvControl`.
+    pub const fn new() -> RecvControl {
+        RecvControl {
+            buf: Buf([0; 256]),
+            len: 0,
+            truncated: false,
+        }
+    }
+
+    /// Returns an iterator over the received control messages. Unknown
+    /// control messages are skipped.
+    pub fn messages(&self) -> ControlMessages<'_> {
+        ControlMessages {
+            buf: &self.buf.0[..self.len],
+        }
+    }
+
+    /// Returns true if not all control messages fit in the buffer, in which
+    /// case the remainder was discarded (`MSG_CTRUNC`).
+    pub const fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    #[cfg_attr(not(feature = "os-poll"), allow(dead_code))]
+    pub(crate) fn buf_mut(&mut self) -> &mut [u8] {
+        &mut self.buf.0
+    }
+
+    #[cfg_attr(not(feature = "os-poll"), allow(dead_code))]
+    pub(crate) fn set_received(&mut self, len: usize, truncated: bool) {
+        self.len = len.min(self.buf.0.len());
+        self.truncated = truncated;
+    }
+}
+
+impl Default for RecvControl {
+    fn default() -> RecvControl {
+        RecvControl::new()
+    }
+}
+
+impl fmt::Debug for RecvControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.messages()).finish()
+    }
+}
+
+/// Iterator over the [`ControlMessage`]s in a [`RecvControl`].
+#[derive(Clone, Debug)]
+pub struct ControlMessages<'a> {
+    buf: &'a [u8],
+}
+
+impl Iterator for ControlMessages<'_> {
+    type Item = ControlMessage;
+
+    fn next(&mut self) -> Option<ControlMessage> {
+        // SAFETY: `CMSG_LEN` is safe to call.
+        let header_len = unsafe { libc::CMSG_LEN(0) } as usize;
+        while self.buf.len() >= header_len {
+            // SAFETY: checked above that the buffer is large enough.
+            let header: libc::cmsghdr = unsafe { ptr::read_unaligned(self.buf.as_ptr().cast()) };
+            let len = header.cmsg_len as usize;
+            if len < header_len || len > self.buf.len() {
+                break;
+            }
+            let data = &self.buf[header_len..len];
+            // SAFETY: `CMSG_SPACE` is safe to call.
+            let space = unsafe { libc::CMSG_SPACE(data.len() as _) } as usize;
+            self.buf = &self.buf[space.min(self.buf.len())..];
+            if let Some(message) = ControlMessage::parse(header.cmsg_level, header.cmsg_type, data)
+            {
+                return Some(message);
+            }
+        }
+        self.buf = &[];
+        None
+    }
+}
@@ -25,7 +25,36 @@
 //! <https://github.com/rust-lang/rust/issues/55794>. The best advice we can
 //! give is to always call receive with a large enough buffer.
 
+#[cfg(unix)]
+mod accept;
+#[cfg(unix)]
+pub use self::accept::{AcceptReserve, AcceptStop, Accepted};
+
+#[cfg(unix)]
+mod batch;
+#[cfg(unix)]
+pub use self::batch::{RecvDatagram, SendDatagram};
+
+mod connect;
+pub use self::connect::ConnectStatus;
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod control;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::control::{ControlMessage, ControlMessages, RecvControl, SendControl};
+
+#[cfg(unix)]
+mod msg_flags;
+#[cfg(unix)]
+pub use self::msg_flags::MsgFlags;
+
 mod tcp;
+#[cfg(not(all(target_os = "wasi", target_env = "p1")))]
+pub use self::tcp::HappyEyeballs;
+#[cfg(unix)]
+pub use self::tcp::TcpSocket;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::tcp::{TcpInfo, TcpState};
 pub use self::tcp::{TcpListener, TcpStream};
 
 #[cfg(not(all(target_os = "wasi", target_env = "p1")))]
@@ -33,6 +62,16 @@ mod udp;
 #[cfg(not(all(target_os = "wasi", target_env = "p1")))]
 pub use self::udp::UdpSocket;
 
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+mod reuseport;
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+pub use self::reuseport::ReuseportGroup;
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod zerocopy;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::zerocopy::{ZerocopyCompletion, ZerocopyCompletions};
+
 #[cfg(unix)]
 mod uds;
 #[cfg(unix)]
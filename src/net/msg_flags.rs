@@ -0,0 +1,151 @@
+use std::{fmt, ops};
+
+/// Flags passed to the `send_with_flags` and `recv_with_flags` family of
+/// methods, e.g. [`TcpStream::send_with_flags`].
+///
+/// Not all flags are supported by all socket types, see the `send(2)` and
+/// `recv(2)` manuals of the OS.
+///
+/// [`TcpStream::send_with_flags`]: crate::net::TcpStream::send_with_flags
+///
+/// # Examples
+///
+/// ```
+/// use mio::net::MsgFlags;
+///
+/// const FLAGS: MsgFlags = MsgFlags::PEEK.add(MsgFlags::TRUNC);
+/// assert!(FLAGS.contains(MsgFlags::PEEK));
+/// assert!(!FLAGS.contains(MsgFlags::OOB));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Default)]
+pub struct MsgFlags(libc::c_int);
+
+impl MsgFlags {
+    /// No flags.
+    pub const EMPTY: MsgFlags = MsgFlags(0);
+
+    /// Receive data without removing it from the queue (`MSG_PEEK`).
+    pub const PEEK: MsgFlags = MsgFlags(libc::MSG_PEEK);
+
+    /// Return the real length of the datagram, or of the discarded data for
+    /// streams, even if it's larger than the buffer (`MSG_TRUNC`).
+    #[cfg(not(any(target_os = "horizon", target_os = "rtems")))]
+    pub const TRUNC: MsgFlags = MsgFlags(libc::MSG_TRUNC);
+
+    /// Wait until the buffer is filled (`MSG_WAITALL`). On a non-blocking
+    /// socket this can still return less data.
+    #[cfg(not(target_os = "nuttx"))]
+    pub const WAITALL: MsgFlags = MsgFlags(libc::MSG_WAITALL);
+
+    /// Don't block (`MSG_DONTWAIT`). Mio sockets are already non-blocking.
+    #[cfg(not(any(target_os = "aix", target_os = "nuttx")))]
+    pub const DONTWAIT: MsgFlags = MsgFlags(libc::MSG_DONTWAIT);
+
+    /// Send or receive out-of-band data (`MSG_OOB`).
+    pub const OOB: MsgFlags = MsgFlags(libc::MSG_OOB);
+
+    /// Don't raise `SIGPIPE` if the peer closed the stream, only return an
+    /// `EPIPE` error (`MSG_NOSIGNAL`).
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "solaris",
+    ))]
+    pub const NOSIGNAL: MsgFlags = MsgFlags(libc::MSG_NOSIGNAL);
+
+    /// More data will be sent, delay sending partial frames (`MSG_MORE`).
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub const MORE: MsgFlags = MsgFlags(libc::MSG_MORE);
+
+    /// Add together two `MsgFlags`.
+    ///
+    /// This does the same thing as the `BitOr` implementation, but is a
+    /// constant function.
+    #[allow(clippy::should_implement_trait)]
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub const fn add(self, other: MsgFlags) -> MsgFlags {
+        MsgFlags(self.0 | other.0)
+    }
+
+    /// Returns true if all flags in `other` are set in `self`.
+    #[must_use]
+    pub const fn contains(self, other: MsgFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns true if no flags are set.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    #[cfg_attr(not(feature = "os-poll"), allow(dead_code))]
+    pub(crate) const fn as_raw(self) -> libc::c_int {
+        self.0
+    }
+}
+
+impl ops::BitOr for MsgFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        self.add(other)
+    }
+}
+
+impl ops::BitOrAssign for MsgFlags {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 = (*self | other).0;
+    }
+}
+
+impl fmt::Debug for MsgFlags {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags: &[(MsgFlags, &str)] = &[
+            (MsgFlags::PEEK, "PEEK"),
+            #[cfg(not(any(target_os = "horizon", target_os = "rtems")))]
+            (MsgFlags::TRUNC, "TRUNC"),
+            #[cfg(not(target_os = "nuttx"))]
+            (MsgFlags::WAITALL, "WAITALL"),
+            #[cfg(not(any(target_os = "aix", target_os = "nuttx")))]
+            (MsgFlags::DONTWAIT, "DONTWAIT"),
+            (MsgFlags::OOB, "OOB"),
+            #[cfg(any(
+                target_os = "android",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "fuchsia",
+                target_os = "illumos",
+                target_os = "linux",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "solaris",
+            ))]
+            (MsgFlags::NOSIGNAL, "NOSIGNAL"),
+            #[cfg(any(target_os = "android", target_os = "linux"))]
+            (MsgFlags::MORE, "MORE"),
+        ];
+        let mut one = false;
+        for (flag, name) in flags {
+            if self.contains(*flag) {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "{name}")?;
+                one = true
+            }
+        }
+        if !one {
+            write!(fmt, "(empty)")?
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use std::io;
+use std::net::SocketAddr;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::os::fd::AsRawFd;
+
+use crate::net::{TcpListener, TcpSocket, UdpSocket};
+use crate::sys;
+
+/// A group of sockets bound to the same address using `SO_REUSEPORT`.
+///
+/// This can be used to create one [`TcpListener`] or [`UdpSocket`] per worker
+/// thread, all bound to the same address.
+///
+/// # Platform specific behavior
+///
+/// Only on Linux and Android does the OS distribute incoming connections (or
+/// datagrams) between the sockets in the group, by default based on a hash of
+/// the connection's addresses. Here the group can optionally steer connections
+/// to the socket for the CPU that received the connection, see
+/// [`ReuseportGroup::steer_by_cpu`].
+///
+/// On other platforms, e.g. FreeBSD, macOS, iOS, OpenBSD, NetBSD and
+/// DragonFly BSD, `SO_REUSEPORT` only allows binding the sockets to the same
+/// address and **all** incoming connections (or datagrams) are delivered to a
+/// single socket in the group, so the load is not balanced between the
+/// sockets. (FreeBSD's `SO_REUSEPORT_LB` is not used.)
+///
+/// Not supported on Solaris and illumos.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::thread;
+///
+/// use mio::net::ReuseportGroup;
+///
+/// let listeners = ReuseportGroup::new(4).bind_tcp("127.0.0.1:0".parse()?)?;
+/// let address = listeners[0].local_addr()?;
+/// assert!(listeners.iter().all(|l| l.local_addr().unwrap() == address));
+///
+/// let workers: Vec<_> = listeners
+///     .into_iter()
+///     .map(|listener| thread::spawn(move || {
+///         // Create a `Poll` instance and register the listener...
+/// #       drop(listener);
+///     }))
+///     .collect();
+/// #   for worker in workers { worker.join().unwrap(); }
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ReuseportGroup {
+    size: usize,
+    backlog: u32,
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    steer_by_cpu: bool,
+}
+
+impl ReuseportGroup {
+    /// Create a new group of `size` sockets.
+    pub const fn new(size: usize) -> ReuseportGroup {
+        ReuseportGroup {
+            size,
+            backlog: 1024,
+            #[cfg(any(target_os = "android", target_os = "linux"))]
+            steer_by_cpu: false,
+        }
+    }
+
+    /// Set the backlog used for the listeners created by
+    /// [`ReuseportGroup::bind_tcp`], defaults to 1024. See
+    /// [`TcpSocket::listen`].
+    pub const fn backlog(mut self, backlog: u32) -> ReuseportGroup {
+        self.backlog = backlog;
+        self
+    }
+
+    /// Steer connections and datagrams to the socket for the CPU that received
+    /// them, defaults to false.
+    ///
+    /// If enabled the group attaches a classic BPF program using
+    /// `SO_ATTACH_REUSEPORT_CBPF` that selects the socket at index `cpu %
+    /// size` in the group, where `cpu` is the value of `SO_INCOMING_CPU`. When
+    /// each socket is used by a thread pinned to the matching CPU this keeps
+    /// all processing of a connection on the same CPU.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub const fn steer_by_cpu(mut self, steer_by_cpu: bool) -> ReuseportGroup {
+        self.steer_by_cpu = steer_by_cpu;
+        self
+    }
+
+    /// Create the listeners of the group, all bound to `addr`.
+    ///
+    /// If the port of `addr` is zero the OS picks a port for the first
+    /// listener, which is also used for the other listeners.
+    ///
+    /// The listeners are returned in group order, i.e. when steering by CPU
+    /// the listener at index `n` receives the connections for CPU `n`.
+    pub fn bind_tcp(&self, addr: SocketAddr) -> io::Result<Vec<TcpListener>> {
+        self.check_size()?;
+        let mut listeners = Vec::with_capacity(self.size);
+        let mut addr = addr;
+        for _ in 0..self.size {
+            let socket = TcpSocket::new_for_addr(addr)?;
+            socket.set_reuseport(true)?;
+            socket.bind(addr)?;
+            let listener = socket.listen(self.backlog)?;
+            addr = listener.local_addr()?;
+            listeners.push(listener);
+        }
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        if self.steer_by_cpu {
+            self.attach_steering(&listeners[0])?;
+        }
+        Ok(listeners)
+    }
+
+    /// Create the UDP sockets of the group, all bound to `addr`.
+    ///
+    /// If the port of `addr` is zero the OS picks a port for the first socket,
+    /// which is also used for the other sockets.
+    ///
+    /// The sockets are returned in group order, i.e. when steering by CPU the
+    /// socket at index `n` receives the datagrams for CPU `n`.
+    pub fn bind_udp(&self, addr: SocketAddr) -> io::Result<Vec<UdpSocket>> {
+        self.check_size()?;
+        let mut sockets = Vec::with_capacity(self.size);
+        let mut addr = addr;
+        for _ in 0..self.size {
+            let socket = sys::udp::bind_reuseport(addr).map(UdpSocket::from_std)?;
+            addr = socket.local_addr()?;
+            sockets.push(socket);
+        }
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        if self.steer_by_cpu {
+            self.attach_steering(&sockets[0])?;
+        }
+        Ok(sockets)
+    }
+
+    fn check_size(&self) -> io::Result<()> {
+        if self.size == 0 {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "reuseport group must contain at least one socket",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The program applies to the entire group, so it only has to be attached
+    /// to a single socket, after all sockets are bound.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    fn attach_steering<S: AsRawFd>(&self, socket: &S) -> io::Result<()> {
+        let size = self.size.try_into().map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "reuseport group too large")
+        })?;
+        sys::steer_reuseport_by_cpu(socket.as_raw_fd(), size)
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use crate::event::Event;
+use crate::net::{ConnectStatus, TcpStream};
+use crate::{Interest, Registry, Token};
+
+/// Connect to one of multiple addresses, racing staggered connection attempts
+/// ("Happy Eyeballs", [RFC 8305]).
+///
+/// The addresses are attempted in order, alternating between IPv6 and IPv4
+/// addresses (starting with the family of the first address). A new attempt is
+/// started when the previous attempt failed or after the [attempt delay]
+/// passed, while earlier attempts continue. The first attempt to connect wins,
+/// all other attempts are cancelled.
+///
+/// `HappyEyeballs` doesn't block or spawn threads, it's a state machine driven
+/// by the caller:
+///  1. [`start`] the first attempt. The attempts are registered with the
+///     `Registry` using tokens derived from the token passed to [`new`], see
+///     [`owns`].
+///  2. Poll using the [`timeout`] (or a smaller one).
+///  3. Pass all events for which [`owns`] returns true to [`handle_event`],
+///     until it returns the connected stream. Call [`handle_timeout`] after
+///     each poll to start the next attempt if the delay passed.
+///
+/// The returned stream is deregistered, it must be registered again by the
+/// caller. If all attempts fail the error of the last attempt is returned.
+///
+/// [RFC 8305]: https://datatracker.ietf.org/doc/html/rfc8305
+/// [attempt delay]: HappyEyeballs::attempt_delay
+/// [`start`]: HappyEyeballs::start
+/// [`new`]: HappyEyeballs::new
+/// [`owns`]: HappyEyeballs::owns
+/// [`timeout`]: HappyEyeballs::timeout
+/// [`handle_event`]: HappyEyeballs::handle_event
+/// [`handle_timeout`]: HappyEyeballs::handle_timeout
+///
+/// # Examples
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::net::SocketAddr;
+///
+/// use mio::net::{HappyEyeballs, TcpListener};
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// # let listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+/// # let port = listener.local_addr()?.port();
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(128);
+///
+/// // Normally these are the result of a DNS lookup.
+/// let addresses: Vec<SocketAddr> = vec![
+///     SocketAddr::new("::1".parse()?, port),
+///     SocketAddr::new("127.0.0.1".parse()?, port),
+/// ];
+/// let mut connect = HappyEyeballs::new(addresses, Token(10));
+/// connect.start(poll.registry())?;
+///
+/// let mut stream = 'connect: loop {
+///     poll.poll(&mut events, connect.timeout())?;
+///     for event in events.iter() {
+///         if connect.owns(event.token()) {
+///             if let Some(stream) = connect.handle_event(poll.registry(), event)? {
+///                 break 'connect stream;
+///             }
+///         }
+///     }
+///     connect.handle_timeout(poll.registry())?;
+/// };
+///
+/// poll.registry().register(&mut stream, Token(0), Interest::READABLE)?;
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct HappyEyeballs {
+    /// Addresses not yet attempted, in order.
+    addresses: VecDeque<SocketAddr>,
+    /// Attempts in progress.
+    attempts: Vec<Attempt>,
+    /// Token used for the first attempt.
+    token: Token,
+    /// Number of attempts started, used to derive the token.
+    started: usize,
+    attempt_delay: Duration,
+    /// When to start the next attempt.
+    next_attempt: Option<Instant>,
+    /// Error of the last failed attempt.
+    last_error: Option<io::Error>,
+}
+
+#[derive(Debug)]
+struct Attempt {
+    stream: TcpStream,
+    token: Token,
+}
+
+impl HappyEyeballs {
+    /// Create a new `HappyEyeballs` connecting to one of `addresses`.
+    ///
+    /// The attempts are registered with tokens starting at `token`, up to
+    /// `token + addresses.len()`. Starting an attempt returns an error if its
+    /// token would overflow.
+    pub fn new<I>(addresses: I, token: Token) -> HappyEyeballs
+    where
+        I: IntoIterator<Item = SocketAddr>,
+    {
+        HappyEyeballs {
+            addresses: interleave(addresses),
+            attempts: Vec::new(),
+            token,
+            started: 0,
+            // Recommended value from RFC 8305, section 5.
+            attempt_delay: Duration::from_millis(250),
+            next_attempt: None,
+            last_error: None,
+        }
+    }
+
+    /// Set the delay between starting attempts, defaults to 250 milliseconds.
+    pub const fn attempt_delay(mut self, delay: Duration) -> HappyEyeballs {
+        self.attempt_delay = delay;
+        self
+    }
+
+    /// Returns true if `token` is used by one of the connection attempts.
+    pub fn owns(&self, token: Token) -> bool {
+        token.0 >= self.token.0 && token.0 - self.token.0 < self.started
+    }
+
+    /// Start the first connection attempt.
+    ///
+    /// Returns an error if there are no addresses to connect to, or if all
+    /// addresses fail immediately.
+    pub fn start(&mut self, registry: &Registry) -> io::Result<()> {
+        if self.addresses.is_empty() && self.attempts.is_empty() {
+            return Err(self.last_error.take().unwrap_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "no addresses to connect to")
+            }));
+        }
+        self.start_attempt(registry)
+    }
+
+    /// Returns the amount of time until the next attempt should be started,
+    /// to be used as timeout in [`Poll::poll`].
+    ///
+    /// [`Poll::poll`]: crate::Poll::poll
+    pub fn timeout(&self) -> Option<Duration> {
+        self.next_attempt
+            .map(|next| next.saturating_duration_since(Instant::now()))
+    }
+
+    /// Start the next attempt if the attempt delay passed.
+    pub fn handle_timeout(&mut self, registry: &Registry) -> io::Result<()> {
+        match self.next_attempt {
+            Some(next) if next <= Instant::now() => self.start_attempt(registry),
+            _ => Ok(()),
+        }
+    }
+
+    /// Handle an `event` for one of the attempts, see [`owns`].
+    ///
+    /// Returns the stream if the attempt connected, cancelling all other
+    /// attempts. Returns an error if all attempts failed.
+    ///
+    /// [`owns`]: HappyEyeballs::owns
+    pub fn handle_event(
+        &mut self,
+        registry: &Registry,
+        event: &Event,
+    ) -> io::Result<Option<TcpStream>> {
+        let Some(index) = self.attempts.iter().position(|a| a.token == event.token()) else {
+            // Attempt already failed or cancelled.
+            return Ok(None);
+        };
+        let status = match self.attempts[index].stream.connect_status() {
+            Ok(status) => status,
+            Err(err) => ConnectStatus::Failed(err),
+        };
+        match status {
+            ConnectStatus::Pending => Ok(None),
+            ConnectStatus::Connected => {
+                let mut attempt = self.attempts.swap_remove(index);
+                self.cancel(registry);
+                // Don't lose the connected stream if deregistering fails, the
+                // stream is registered again by the caller anyway.
+                let _ = registry.deregister(&mut attempt.stream);
+                Ok(Some(attempt.stream))
+            }
+            ConnectStatus::Failed(err) => {
+                let mut attempt = self.attempts.swap_remove(index);
+                let _ = registry.deregister(&mut attempt.stream);
+                self.last_error = Some(err);
+                if self.addresses.is_empty() && self.attempts.is_empty() {
+                    return Err(self.last_error.take().unwrap());
+                }
+                // Don't wait for the delay to start the next attempt.
+                if !self.addresses.is_empty() {
+                    self.start_attempt(registry)?;
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// Cancel all attempts in progress and don't start new attempts.
+    pub fn cancel(&mut self, registry: &Registry) {
+        for mut attempt in self.attempts.drain(..) {
+            // Closing the stream deregisters it as well, but not on all
+            // platforms.
+            let _ = registry.deregister(&mut attempt.stream);
+        }
+        self.addresses.clear();
+        self.next_attempt = None;
+    }
+
+    /// Start an attempt for the next address, skipping addresses that fail
+    /// immediately.
+    fn start_attempt(&mut self, registry: &Registry) -> io::Result<()> {
+        while let Some(address) = self.addresses.pop_front() {
+            let Some(token) = self.token.0.checked_add(self.started).map(Token) else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "attempt token overflows",
+                ));
+            };
+            self.started += 1;
+            let result = TcpStream::connect(address).and_then(|mut stream| {
+                registry.register(&mut stream, token, Interest::WRITABLE)?;
+                Ok(stream)
+            });
+            match result {
+                Ok(stream) => {
+                    self.attempts.push(Attempt { stream, token });
+                    self.next_attempt = if self.addresses.is_empty() {
+                        None
+                    } else {
+                        Some(Instant::now() + self.attempt_delay)
+                    };
+                    return Ok(());
+                }
+                Err(err) => self.last_error = Some(err),
+            }
+        }
+
+        self.next_attempt = None;
+        if self.attempts.is_empty() {
+            // All addresses failed immediately.
+            Err(self.last_error.take().unwrap())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Interleave the IPv6 and IPv4 addresses, starting with the family of the
+/// first address, see RFC 8305 section 4.
+fn interleave<I>(addresses: I) -> VecDeque<SocketAddr>
+where
+    I: IntoIterator<Item = SocketAddr>,
+{
+    let addresses: Vec<SocketAddr> = addresses.into_iter().collect();
+    let first_is_ipv6 = addresses.first().is_some_and(SocketAddr::is_ipv6);
+    let (mut first, mut second): (VecDeque<_>, VecDeque<_>) = addresses
+        .into_iter()
+        .partition(|address| address.is_ipv6() == first_is_ipv6);
+    let mut interleaved = VecDeque::with_capacity(first.len() + second.len());
+    loop {
+        match (first.pop_front(), second.pop_front()) {
+            (None, None) => return interleaved,
+            (a, b) => interleaved.extend(a.into_iter().chain(b)),
+        }
+    }
+}
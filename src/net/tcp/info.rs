@@ -0,0 +1,355 @@
+use std::mem::{size_of, MaybeUninit};
+use std::ptr::addr_of;
+use std::time::Duration;
+
+/// Snapshot of the TCP state of a [`TcpStream`], see [`TcpStream::tcp_info`].
+///
+/// This is based on `struct tcp_info` as returned by the `TCP_INFO` socket
+/// option. Fields that are not supported by the running kernel (added in later
+/// versions) are returned as `None`.
+///
+/// [`TcpStream`]: crate::net::TcpStream
+/// [`TcpStream::tcp_info`]: crate::net::TcpStream::tcp_info
+#[derive(Copy, Clone)]
+pub struct TcpInfo {
+    raw: RawTcpInfo,
+    /// Number of bytes of `raw` set by the kernel.
+    length: usize,
+}
+
+/// State of a TCP connection, see [`TcpInfo::state`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TcpState {
+    /// Connection is established.
+    Established,
+    /// Sent a connection request, waiting for a matching request.
+    SynSent,
+    /// Received a connection request, waiting for an acknowledgement.
+    SynReceived,
+    /// Local side closed, waiting for the peer to acknowledge or close.
+    FinWait1,
+    /// Local side closed and acknowledged, waiting for the peer to close.
+    FinWait2,
+    /// Both sides closed, waiting to ensure the peer received the
+    /// acknowledgement.
+    TimeWait,
+    /// Connection is closed.
+    Closed,
+    /// Peer closed, waiting for the local side to close.
+    CloseWait,
+    /// Both sides closed, waiting for the acknowledgement of the local close.
+    LastAck,
+    /// Listening for connections.
+    Listen,
+    /// Both sides closed simultaneously, waiting for the acknowledgement.
+    Closing,
+    /// A state not known to Mio, containing the raw value.
+    Unknown(u8),
+}
+
+impl TcpState {
+    const fn from_raw(state: u8) -> TcpState {
+        // See `enum` in `include/net/tcp_states.h`.
+        match state {
+            1 => TcpState::Established,
+            2 => TcpState::SynSent,
+            3 => TcpState::SynReceived,
+            4 => TcpState::FinWait1,
+            5 => TcpState::FinWait2,
+            6 => TcpState::TimeWait,
+            7 => TcpState::Closed,
+            8 => TcpState::CloseWait,
+            9 => TcpState::LastAck,
+            10 => TcpState::Listen,
+            11 => TcpState::Closing,
+            state => TcpState::Unknown(state),
+        }
+    }
+}
+
+/// Returns `Some($field)` if `$field` is set by the kernel, `None` otherwise.
+macro_rules! optional {
+    ($info: expr, $field: ident) => {{
+        let raw = MaybeUninit::<RawTcpInfo>::uninit();
+        let base = raw.as_ptr();
+        // SAFETY: only computes the address of the field, doesn't read it.
+        let field = unsafe { addr_of!((*base).$field) };
+        let end = (field as usize - base as usize) + size_of_pointee(field);
+        if $info.length >= end {
+            Some($info.raw.$field)
+        } else {
+            None
+        }
+    }};
+}
+
+const fn size_of_pointee<T>(_: *const T) -> usize {
+    size_of::<T>()
+}
+
+const fn micros(micros: u32) -> Duration {
+    Duration::from_micros(micros as u64)
+}
+
+impl TcpInfo {
+    pub(crate) const fn new(raw: RawTcpInfo, length: usize) -> TcpInfo {
+        TcpInfo { raw, length }
+    }
+
+    /// State of the connection.
+    pub const fn state(&self) -> TcpState {
+        TcpState::from_raw(self.raw.state)
+    }
+
+    /// Number of consecutive retransmissions of the oldest unacknowledged
+    /// segment, i.e. the current retransmission backoff.
+    pub const fn retransmits(&self) -> u8 {
+        self.raw.retransmits
+    }
+
+    /// Retransmission timeout.
+    pub const fn rto(&self) -> Duration {
+        micros(self.raw.rto)
+    }
+
+    /// Delayed acknowledgement timeout.
+    pub const fn ato(&self) -> Duration {
+        micros(self.raw.ato)
+    }
+
+    /// Maximum segment size for sending.
+    pub const fn send_mss(&self) -> u32 {
+        self.raw.snd_mss
+    }
+
+    /// Maximum segment size for receiving.
+    pub const fn recv_mss(&self) -> u32 {
+        self.raw.rcv_mss
+    }
+
+    /// Number of segments sent but not yet acknowledged.
+    pub const fn unacked(&self) -> u32 {
+        self.raw.unacked
+    }
+
+    /// Number of segments considered lost.
+    pub const fn lost(&self) -> u32 {
+        self.raw.lost
+    }
+
+    /// Number of segments currently being retransmitted.
+    pub const fn retrans(&self) -> u32 {
+        self.raw.retrans
+    }
+
+    /// Path maximum transmission unit.
+    pub const fn path_mtu(&self) -> u32 {
+        self.raw.pmtu
+    }
+
+    /// Smoothed round trip time.
+    pub const fn rtt(&self) -> Duration {
+        micros(self.raw.rtt)
+    }
+
+    /// Round trip time variance.
+    pub const fn rtt_var(&self) -> Duration {
+        micros(self.raw.rttvar)
+    }
+
+    /// Slow start threshold, in segments.
+    pub const fn send_ssthresh(&self) -> u32 {
+        self.raw.snd_ssthresh
+    }
+
+    /// Congestion window, in segments.
+    pub const fn send_cwnd(&self) -> u32 {
+        self.raw.snd_cwnd
+    }
+
+    /// Round trip time estimated by the receiver.
+    pub const fn recv_rtt(&self) -> Duration {
+        micros(self.raw.rcv_rtt)
+    }
+
+    /// Receive window advertised to the peer, in bytes.
+    pub const fn recv_space(&self) -> u32 {
+        self.raw.rcv_space
+    }
+
+    /// Total number of retransmitted segments over the lifetime of the
+    /// connection.
+    pub const fn total_retrans(&self) -> u32 {
+        self.raw.total_retrans
+    }
+
+    /// Current pacing rate, in bytes per second.
+    pub fn pacing_rate(&self) -> Option<u64> {
+        optional!(self, pacing_rate)
+    }
+
+    /// Number of bytes acknowledged by the peer.
+    pub fn bytes_acked(&self) -> Option<u64> {
+        optional!(self, bytes_acked)
+    }
+
+    /// Number of bytes received from the peer.
+    pub fn bytes_received(&self) -> Option<u64> {
+        optional!(self, bytes_received)
+    }
+
+    /// Number of segments sent.
+    pub fn segs_out(&self) -> Option<u32> {
+        optional!(self, segs_out)
+    }
+
+    /// Number of segments received.
+    pub fn segs_in(&self) -> Option<u32> {
+        optional!(self, segs_in)
+    }
+
+    /// Number of bytes written but not yet sent.
+    pub fn notsent_bytes(&self) -> Option<u32> {
+        optional!(self, notsent_bytes)
+    }
+
+    /// Minimum round trip time observed.
+    pub fn min_rtt(&self) -> Option<Duration> {
+        optional!(self, min_rtt).map(micros)
+    }
+
+    /// Most recent delivery rate, in bytes per second.
+    pub fn delivery_rate(&self) -> Option<u64> {
+        optional!(self, delivery_rate)
+    }
+
+    /// Number of bytes sent, including retransmissions.
+    pub fn bytes_sent(&self) -> Option<u64> {
+        optional!(self, bytes_sent)
+    }
+
+    /// Number of bytes retransmitted.
+    pub fn bytes_retrans(&self) -> Option<u64> {
+        optional!(self, bytes_retrans)
+    }
+
+    /// Send window advertised by the peer, in bytes.
+    pub fn send_window(&self) -> Option<u32> {
+        optional!(self, snd_wnd)
+    }
+}
+
+impl std::fmt::Debug for TcpInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcpInfo")
+            .field("state", &self.state())
+            .field("retransmits", &self.retransmits())
+            .field("rto", &self.rto())
+            .field("ato", &self.ato())
+            .field("send_mss", &self.send_mss())
+            .field("recv_mss", &self.recv_mss())
+            .field("unacked", &self.unacked())
+            .field("lost", &self.lost())
+            .field("retrans", &self.retrans())
+            .field("path_mtu", &self.path_mtu())
+            .field("rtt", &self.rtt())
+            .field("rtt_var", &self.rtt_var())
+            .field("send_ssthresh", &self.send_ssthresh())
+            .field("send_cwnd", &self.send_cwnd())
+            .field("recv_rtt", &self.recv_rtt())
+            .field("recv_space", &self.recv_space())
+            .field("total_retrans", &self.total_retrans())
+            .field("pacing_rate", &self.pacing_rate())
+            .field("bytes_acked", &self.bytes_acked())
+            .field("bytes_received", &self.bytes_received())
+            .field("segs_out", &self.segs_out())
+            .field("segs_in", &self.segs_in())
+            .field("notsent_bytes", &self.notsent_bytes())
+            .field("min_rtt", &self.min_rtt())
+            .field("delivery_rate", &self.delivery_rate())
+            .field("bytes_sent", &self.bytes_sent())
+            .field("bytes_retrans", &self.bytes_retrans())
+            .field("send_window", &self.send_window())
+            .finish()
+    }
+}
+
+/// `struct tcp_info` as defined in `include/uapi/linux/tcp.h`, up to
+/// `tcpi_snd_wnd`.
+///
+/// Not using the libc definition as it differs between the C libraries and
+/// doesn't always include the later fields.
+#[repr(C)]
+#[derive(Copy, Clone)]
+#[allow(dead_code)] // Not all fields are exposed, but they're needed for the layout.
+pub(crate) struct RawTcpInfo {
+    state: u8,
+    ca_state: u8,
+    retransmits: u8,
+    probes: u8,
+    backoff: u8,
+    options: u8,
+    wscale: u8,
+    flags: u8,
+
+    rto: u32,
+    ato: u32,
+    snd_mss: u32,
+    rcv_mss: u32,
+
+    unacked: u32,
+    sacked: u32,
+    lost: u32,
+    retrans: u32,
+    fackets: u32,
+
+    last_data_sent: u32,
+    last_ack_sent: u32,
+    last_data_recv: u32,
+    last_ack_recv: u32,
+
+    pmtu: u32,
+    rcv_ssthresh: u32,
+    rtt: u32,
+    rttvar: u32,
+    snd_ssthresh: u32,
+    snd_cwnd: u32,
+    advmss: u32,
+    reordering: u32,
+
+    rcv_rtt: u32,
+    rcv_space: u32,
+
+    total_retrans: u32,
+
+    pacing_rate: u64,
+    max_pacing_rate: u64,
+    bytes_acked: u64,
+    bytes_received: u64,
+    segs_out: u32,
+    segs_in: u32,
+
+    notsent_bytes: u32,
+    min_rtt: u32,
+    data_segs_in: u32,
+    data_segs_out: u32,
+
+    delivery_rate: u64,
+
+    busy_time: u64,
+    rwnd_limited: u64,
+    sndbuf_limited: u64,
+
+    delivered: u32,
+    delivered_ce: u32,
+
+    bytes_sent: u64,
+    bytes_retrans: u64,
+    dsack_dups: u32,
+    reord_seen: u32,
+
+    rcv_ooopack: u32,
+
+    snd_wnd: u32,
+}
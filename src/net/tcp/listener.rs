@@ -9,10 +9,16 @@ use std::os::hermit::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, Owned
 use std::os::windows::io::{
     AsRawSocket, AsSocket, BorrowedSocket, FromRawSocket, IntoRawSocket, OwnedSocket, RawSocket,
 };
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::time::Duration;
 use std::{fmt, io};
 
 use crate::io_source::IoSource;
+#[cfg(unix)]
+use crate::net::accept::accept_many;
 use crate::net::TcpStream;
+#[cfg(unix)]
+use crate::net::{AcceptReserve, AcceptStop, Accepted};
 #[cfg(any(
     unix,
     target_os = "hermit",
@@ -63,6 +69,11 @@ impl TcpListener {
     /// 2. Set the `SO_REUSEADDR` option on the socket on Unix.
     /// 3. Bind the socket to the specified address.
     /// 4. Calls `listen` on the socket to prepare it to receive new connections.
+    ///
+    /// To set other options or use a different backlog use [`TcpSocket`]
+    /// (Unix only).
+    ///
+    /// [`TcpSocket`]: crate::net::TcpSocket
     #[cfg(not(all(target_os = "wasi", target_env = "p1")))]
     pub fn bind(addr: SocketAddr) -> io::Result<TcpListener> {
         let socket = new_for_addr(addr)?;
@@ -79,9 +90,9 @@ impl TcpListener {
         // which allows “socket hijacking”, so we explicitly don't set it here.
         // https://docs.microsoft.com/en-us/windows/win32/winsock/using-so-reuseaddr-and-so-exclusiveaddruse
         #[cfg(not(windows))]
-        set_reuseaddr(&listener.inner, true)?;
+        set_reuseaddr(&*listener.inner, true)?;
 
-        bind(&listener.inner, addr)?;
+        bind(&*listener.inner, addr)?;
         // Use the same backlog value as the standard library.
         // <https://github.com/rust-lang/rust/blob/0028f344ce9f64766259577c998a1959ca1f6a0b/library/std/src/sys/net/connection/socket/mod.rs#L559-L571>
         let backlog = if cfg!(target_os = "horizon") {
@@ -91,7 +102,7 @@ impl TcpListener {
         } else {
             128
         };
-        listen(&listener.inner, backlog)?;
+        listen(&*listener.inner, backlog)?;
         Ok(listener)
     }
 
@@ -121,6 +132,46 @@ impl TcpListener {
         })
     }
 
+    /// Accepts up to `max` new `TcpStream`s, adding them to `streams`.
+    ///
+    /// This is equivalent to calling [`accept`] in a loop, stopping when `max`
+    /// streams are accepted, when no more streams are ready (`WouldBlock`) or
+    /// when accepting fails because of a lack of resources (such as `EMFILE`).
+    /// The returned [`AcceptStop`] reports which of these happened. Connections
+    /// aborted by the peer before they're accepted are skipped.
+    ///
+    /// If any other error occurs it is returned, the streams accepted before
+    /// the error stay in `streams`.
+    ///
+    /// [`accept`]: TcpListener::accept
+    #[cfg(unix)]
+    pub fn accept_many(
+        &self,
+        streams: &mut Vec<(TcpStream, SocketAddr)>,
+        max: usize,
+    ) -> io::Result<AcceptStop> {
+        accept_many(streams, max, || self.accept())
+    }
+
+    /// Accepts a new `TcpStream`, shedding the connection if the process ran
+    /// out of file descriptors.
+    ///
+    /// If accepting fails with `EMFILE` or `ENFILE` the file descriptor
+    /// reserved by `reserve` is used to accept the connection, which is closed
+    /// directly and returned as [`Accepted::Shed`]. This removes the
+    /// connection from the accept queue, so the listener keeps reporting
+    /// events for new connections. Like [`accept`] this should be called until
+    /// it returns a `WouldBlock` error.
+    ///
+    /// [`accept`]: TcpListener::accept
+    #[cfg(unix)]
+    pub fn accept_reserved(
+        &self,
+        reserve: &mut AcceptReserve,
+    ) -> io::Result<Accepted<TcpStream, SocketAddr>> {
+        reserve.accept(|| self.accept())
+    }
+
     /// Returns the local socket address of this listener.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         self.inner.local_addr()
@@ -143,6 +194,46 @@ impl TcpListener {
         self.inner.ttl()
     }
 
+    /// Sets the value of the `TCP_FASTOPEN` option on this socket, enabling TCP
+    /// Fast Open.
+    ///
+    /// `queue_length` is the maximum number of pending Fast Open requests, i.e.
+    /// connections for which data was received in the SYN, but that are not yet
+    /// accepted. Zero disables Fast Open. Fast Open must also be enabled for
+    /// servers in the `net.ipv4.tcp_fastopen` sysctl.
+    ///
+    /// This can also be set before listening, using [`TcpSocket::set_fastopen`].
+    ///
+    /// [`TcpSocket::set_fastopen`]: crate::net::TcpSocket::set_fastopen
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_fastopen(&self, queue_length: u32) -> io::Result<()> {
+        sys::tcp::set_fastopen(&*self.inner, queue_length)
+    }
+
+    /// Gets the value of the `TCP_FASTOPEN` option on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn fastopen(&self) -> io::Result<u32> {
+        sys::tcp::fastopen(&*self.inner)
+    }
+
+    /// Sets the value of the `TCP_DEFER_ACCEPT` option on this socket.
+    ///
+    /// If set, a connection is only ready to be accepted once data has arrived
+    /// from the peer, or the `timeout` has passed, i.e. a readable event is only
+    /// returned once the client has sent its request. `None` disables the
+    /// option. The timeout has a resolution of seconds, the OS may round it to
+    /// the next SYN-ACK retransmission.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_defer_accept(&self, timeout: Option<Duration>) -> io::Result<()> {
+        sys::tcp::set_defer_accept(&*self.inner, timeout)
+    }
+
+    /// Gets the value of the `TCP_DEFER_ACCEPT` option on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn defer_accept(&self) -> io::Result<Option<Duration>> {
+        sys::tcp::defer_accept(&*self.inner)
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -237,6 +328,19 @@ impl From<OwnedFd> for TcpListener {
     }
 }
 
+#[cfg(all(unix, feature = "os-ext"))]
+impl<T: AsFd + Into<OwnedFd>> From<crate::unix::OwnedSource<T>> for TcpListener {
+    /// Converts an `OwnedSource` to a `TcpListener`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the FD is in non-blocking
+    /// mode and that it's deregistered from any `Poll` instance.
+    fn from(source: crate::unix::OwnedSource<T>) -> Self {
+        TcpListener::from(OwnedFd::from(source))
+    }
+}
+
 #[cfg(windows)]
 impl IntoRawSocket for TcpListener {
     fn into_raw_socket(self) -> RawSocket {
@@ -1,5 +1,20 @@
+#[cfg(not(all(target_os = "wasi", target_env = "p1")))]
+mod happy_eyeballs;
+#[cfg(not(all(target_os = "wasi", target_env = "p1")))]
+pub use self::happy_eyeballs::HappyEyeballs;
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod info;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::info::{TcpInfo, TcpState};
+
 mod listener;
 pub use self::listener::TcpListener;
 
+#[cfg(unix)]
+mod socket;
+#[cfg(unix)]
+pub use self::socket::TcpSocket;
+
 mod stream;
 pub use self::stream::TcpStream;
@@ -0,0 +1,275 @@
+use std::io;
+use std::net::{self, SocketAddr};
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
+
+use crate::net::{TcpListener, TcpStream};
+use crate::sys::tcp::{
+    bind, connect, listen, local_addr, new_for_addr, only_v6, recv_buffer_size, reuseaddr,
+    send_buffer_size, set_only_v6, set_recv_buffer_size, set_reuseaddr, set_send_buffer_size,
+};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys::tcp::{fastopen, set_fastopen};
+#[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+use crate::sys::tcp::{reuseport, set_reuseport};
+
+/// A non-blocking TCP socket used to configure a stream or listener.
+///
+/// `TcpSocket` wraps an operating system socket that is not yet connected or
+/// listening. It can be used to set options that need to be set before
+/// binding, connecting or listening, e.g. `SO_REUSEPORT`, or to bind the
+/// socket to a specific local address before connecting.
+///
+/// The socket can be converted into a [`TcpStream`] by calling [`connect`], or
+/// into a [`TcpListener`] by calling [`listen`].
+///
+/// The socket will be closed when the value is dropped.
+///
+/// [`connect`]: TcpSocket::connect
+/// [`listen`]: TcpSocket::listen
+///
+/// # Examples
+///
+/// Creating a listener with a custom backlog.
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::TcpSocket;
+///
+/// let address = "127.0.0.1:0".parse()?;
+/// let socket = TcpSocket::new_for_addr(address)?;
+/// socket.set_reuseaddr(true)?;
+/// socket.set_recv_buffer_size(64 * 1024)?;
+/// socket.bind(address)?;
+///
+/// let listener = socket.listen(1024)?;
+/// #     drop(listener);
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// Connecting from a specific local address.
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::{TcpListener, TcpSocket};
+///
+/// # let listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+/// # let address = listener.local_addr()?;
+/// let socket = TcpSocket::new_v4()?;
+/// socket.bind("127.0.0.1:0".parse()?)?;
+///
+/// // Like `TcpStream::connect` the connection might not be established yet.
+/// let stream = socket.connect(address)?;
+/// #     drop(stream);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TcpSocket {
+    inner: OwnedFd,
+}
+
+impl TcpSocket {
+    /// Create a new IPv4 TCP socket.
+    ///
+    /// This calls `socket(2)`.
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        TcpSocket::new_for_addr(SocketAddr::new(net::Ipv4Addr::UNSPECIFIED.into(), 0))
+    }
+
+    /// Create a new IPv6 TCP socket.
+    ///
+    /// This calls `socket(2)`.
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        TcpSocket::new_for_addr(SocketAddr::new(net::Ipv6Addr::UNSPECIFIED.into(), 0))
+    }
+
+    /// Create a new TCP socket of the same family as `addr`, i.e. IPv4 or
+    /// IPv6.
+    ///
+    /// This calls `socket(2)`.
+    pub fn new_for_addr(addr: SocketAddr) -> io::Result<TcpSocket> {
+        let socket = new_for_addr(addr)?;
+        // SAFETY: `new_for_addr` returns a new socket on success.
+        Ok(unsafe { TcpSocket::from_raw_fd(socket) })
+    }
+
+    /// Bind the socket to `addr`.
+    ///
+    /// This calls `bind(2)`.
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        bind(&self.inner, addr)
+    }
+
+    /// Connect the socket to `addr`, converting it into a [`TcpStream`].
+    ///
+    /// This calls `connect(2)`. Like [`TcpStream::connect`] the connection
+    /// might not be established once this returns, see its documentation for
+    /// how to determine if the connection is established.
+    pub fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        connect(&self.inner, addr)?;
+        Ok(TcpStream::from(self.inner))
+    }
+
+    /// Start listening on the socket, converting it into a [`TcpListener`].
+    ///
+    /// `backlog` is the maximum number of pending connections, values larger
+    /// than supported by the OS are silently capped by the OS.
+    ///
+    /// This calls `listen(2)`.
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let backlog = backlog.try_into().unwrap_or(i32::MAX);
+        listen(&self.inner, backlog)?;
+        Ok(TcpListener::from(self.inner))
+    }
+
+    /// Sets the value of `SO_REUSEADDR` on this socket.
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        set_reuseaddr(&self.inner, reuseaddr)
+    }
+
+    /// Get the value of `SO_REUSEADDR` set on this socket.
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        reuseaddr(&self.inner)
+    }
+
+    /// Sets the value of `SO_REUSEPORT` on this socket.
+    ///
+    /// This allows multiple sockets to bind to the same address and port. Not
+    /// supported on Solaris and illumos.
+    #[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        set_reuseport(&self.inner, reuseport)
+    }
+
+    /// Get the value of `SO_REUSEPORT` set on this socket.
+    #[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+    pub fn reuseport(&self) -> io::Result<bool> {
+        reuseport(&self.inner)
+    }
+
+    /// Sets the value of `IPV6_V6ONLY` on this socket.
+    ///
+    /// If set to `true` an IPv6 socket only accepts IPv6 connections, otherwise
+    /// it also accepts IPv4 connections (using IPv4-mapped IPv6 addresses). The
+    /// default depends on the OS. Only valid for IPv6 sockets and must be set
+    /// before calling [`bind`].
+    ///
+    /// [`bind`]: TcpSocket::bind
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        set_only_v6(&self.inner, only_v6)
+    }
+
+    /// Get the value of `IPV6_V6ONLY` set on this socket.
+    pub fn only_v6(&self) -> io::Result<bool> {
+        only_v6(&self.inner)
+    }
+
+    /// Sets the value of `SO_SNDBUF` on this socket.
+    ///
+    /// Note that the OS may change the value, e.g. Linux doubles it, use
+    /// [`send_buffer_size`] to get the actual value.
+    ///
+    /// [`send_buffer_size`]: TcpSocket::send_buffer_size
+    pub fn set_send_buffer_size(&self, size: u32) -> io::Result<()> {
+        set_send_buffer_size(&self.inner, size)
+    }
+
+    /// Get the value of `SO_SNDBUF` set on this socket.
+    pub fn send_buffer_size(&self) -> io::Result<u32> {
+        send_buffer_size(&self.inner)
+    }
+
+    /// Sets the value of `SO_RCVBUF` on this socket.
+    ///
+    /// Note that the OS may change the value, e.g. Linux doubles it, use
+    /// [`recv_buffer_size`] to get the actual value.
+    ///
+    /// [`recv_buffer_size`]: TcpSocket::recv_buffer_size
+    pub fn set_recv_buffer_size(&self, size: u32) -> io::Result<()> {
+        set_recv_buffer_size(&self.inner, size)
+    }
+
+    /// Get the value of `SO_RCVBUF` set on this socket.
+    pub fn recv_buffer_size(&self) -> io::Result<u32> {
+        recv_buffer_size(&self.inner)
+    }
+
+    /// Sets the value of the `TCP_FASTOPEN` option on this socket, enabling TCP
+    /// Fast Open for the listener created by [`listen`].
+    ///
+    /// See [`TcpListener::set_fastopen`] for more information.
+    ///
+    /// [`listen`]: TcpSocket::listen
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_fastopen(&self, queue_length: u32) -> io::Result<()> {
+        set_fastopen(&self.inner, queue_length)
+    }
+
+    /// Gets the value of the `TCP_FASTOPEN` option on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn fastopen(&self) -> io::Result<u32> {
+        fastopen(&self.inner)
+    }
+
+    /// Returns the local address of this socket.
+    ///
+    /// Will return an error if the socket is not bound.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        local_addr(&self.inner)
+    }
+}
+
+impl IntoRawFd for TcpSocket {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_raw_fd()
+    }
+}
+
+impl AsRawFd for TcpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl FromRawFd for TcpSocket {
+    /// Converts a `RawFd` to a `TcpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> TcpSocket {
+        TcpSocket {
+            inner: OwnedFd::from_raw_fd(fd),
+        }
+    }
+}
+
+impl From<TcpSocket> for OwnedFd {
+    fn from(tcp_socket: TcpSocket) -> Self {
+        tcp_socket.inner
+    }
+}
+
+impl AsFd for TcpSocket {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.inner.as_fd()
+    }
+}
+
+impl From<OwnedFd> for TcpSocket {
+    /// Converts a `OwnedFd` to a `TcpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the socket is in
+    /// non-blocking mode.
+    fn from(fd: OwnedFd) -> Self {
+        TcpSocket { inner: fd }
+    }
+}
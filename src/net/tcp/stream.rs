@@ -1,8 +1,14 @@
 use std::fmt;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::fs::File;
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
+#[cfg(unix)]
+use std::mem::MaybeUninit;
 use std::net::{self, Shutdown, SocketAddr};
 #[cfg(any(unix, target_os = "wasi"))]
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
+#[cfg(unix)]
+use std::time::Duration;
 // TODO: once <https://github.com/rust-lang/rust/issues/126198> is fixed this
 // can use `std::os::fd` and be merged with the above.
 #[cfg(target_os = "hermit")]
@@ -13,8 +19,37 @@ use std::os::windows::io::{
 };
 
 use crate::io_source::IoSource;
+use crate::net::connect::connect_status;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::zerocopy::ErrorQueue;
+use crate::net::ConnectStatus;
+#[cfg(unix)]
+use crate::net::MsgFlags;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::{TcpInfo, ZerocopyCompletion, ZerocopyCompletions};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys::tcp::{
+    at_mark, congestion, connect_with_data, cork, notsent_lowat, quickack, sendfile,
+    set_congestion, set_cork, set_notsent_lowat, set_quickack, set_user_timeout, tcp_info,
+    user_timeout,
+};
 #[cfg(not(all(target_os = "wasi", target_env = "p1")))]
 use crate::sys::tcp::{connect, new_for_addr};
+#[cfg(unix)]
+use crate::sys::tcp::{
+    keepalive, linger, oobinline, recv_lowat, set_keepalive, set_linger, set_oobinline,
+    set_recv_lowat,
+};
+cfg_tcp_keepalive! {
+    use crate::sys::tcp::{
+        keepalive_interval, keepalive_retries, keepalive_time, set_keepalive_interval,
+        set_keepalive_retries, set_keepalive_time,
+    };
+}
+#[cfg(unix)]
+use crate::sys::{recv_uninit, recv_with_flags, send_with_flags};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys::{recv_zerocopy_completion, send_zerocopy, set_zerocopy, zerocopy};
 use crate::{event, Interest, Registry, Token};
 
 /// A non-blocking TCP stream between a local socket and a remote socket.
@@ -66,27 +101,28 @@ impl TcpStream {
     ///
     /// Ensuring the returned stream is connected is surprisingly complex when
     /// considering cross-platform support. Doing this properly should follow
-    /// the steps below, an example implementation can be found
-    /// [here](https://github.com/Thomasdezeeuw/heph/blob/0c4f1ab3eaf08bea1d65776528bfd6114c9f8374/src/net/tcp/stream.rs#L560-L622).
+    /// the steps below.
     ///
     ///  1. Call `TcpStream::connect`
     ///  2. Register the returned stream with at least [write interest].
     ///  3. Wait for a (writable) event.
-    ///  4. Check `TcpStream::take_error`. If it returns an error, then
-    ///     something went wrong. If it returns `Ok(None)`, then proceed to
-    ///     step 5.
-    ///  5. Check `TcpStream::peer_addr`. If it returns `libc::EINPROGRESS` or
-    ///     `ErrorKind::NotConnected` it means the stream is not yet connected,
-    ///     go back to step 3. If it returns an address it means the stream is
-    ///     connected, go to step 6. If another error is returned something
-    ///     went wrong.
-    ///  6. Now the stream can be used.
+    ///  4. Check [`TcpStream::connect_status`]. If it returns
+    ///     [`ConnectStatus::Pending`] the stream is not yet connected, go back
+    ///     to step 3. If it returns [`ConnectStatus::Failed`] (or an error)
+    ///     something went wrong.
+    ///  5. Now the stream can be used.
     ///
     /// This may return a `WouldBlock` in which case the socket connection
     /// cannot be completed immediately, it usually means there are insufficient
     /// entries in the routing cache.
     ///
+    /// To bind to a local address or set options before connecting use
+    /// [`TcpSocket`] (Unix only).
+    ///
     /// [write interest]: Interest::WRITABLE
+    /// [`ConnectStatus::Pending`]: crate::net::ConnectStatus::Pending
+    /// [`ConnectStatus::Failed`]: crate::net::ConnectStatus::Failed
+    /// [`TcpSocket`]: crate::net::TcpSocket
     #[cfg(not(all(target_os = "wasi", target_env = "p1")))]
     pub fn connect(addr: SocketAddr) -> io::Result<TcpStream> {
         let socket = new_for_addr(addr)?;
@@ -94,10 +130,35 @@ impl TcpStream {
         let stream = unsafe { TcpStream::from_raw_fd(socket) };
         #[cfg(windows)]
         let stream = unsafe { TcpStream::from_raw_socket(socket as _) };
-        connect(&stream.inner, addr)?;
+        connect(&*stream.inner, addr)?;
         Ok(stream)
     }
 
+    /// Create a new TCP stream and issue a non-blocking connect to the
+    /// specified address using TCP Fast Open, sending (part of) `data` in the
+    /// SYN.
+    ///
+    /// Returns the stream and the number of bytes of `data` that are sent with
+    /// the SYN. The remaining bytes must be written once the stream is
+    /// connected, see [`TcpStream::connect`] for how to determine that.
+    ///
+    /// Data can only be sent in the SYN if the peer supports Fast Open and a
+    /// cookie was received from it in a previous connection, otherwise this
+    /// returns zero bytes, requesting a cookie for the next connection. If
+    /// Fast Open is not supported, or disabled (see the `net.ipv4.tcp_fastopen`
+    /// sysctl), this is the same as calling [`TcpStream::connect`] and also
+    /// returns zero bytes.
+    ///
+    /// Note that data sent in the SYN can be delivered to the peer more than
+    /// once, it should be idempotent.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn connect_with_data(addr: SocketAddr, data: &[u8]) -> io::Result<(TcpStream, usize)> {
+        let socket = new_for_addr(addr)?;
+        let stream = unsafe { TcpStream::from_raw_fd(socket) };
+        let n = connect_with_data(&*stream.inner, addr, data)?;
+        Ok((stream, n))
+    }
+
     /// Creates a new `TcpStream` from a standard `net::TcpStream`.
     ///
     /// This function is intended to be used to wrap a TCP stream from the
@@ -116,6 +177,23 @@ impl TcpStream {
         }
     }
 
+    /// Returns the status of a non-blocking connect, e.g. one started by
+    /// [`TcpStream::connect`].
+    ///
+    /// This should be called after receiving a writable event. It checks for
+    /// a connect error using [`take_error`] (clearing it) and whether the
+    /// stream has a peer address, handling the differences between the
+    /// platforms. A spurious writable event returns [`ConnectStatus::Pending`].
+    ///
+    /// Because the error is cleared [`ConnectStatus::Failed`] is returned only
+    /// once, calling this method again returns [`ConnectStatus::Pending`] (or
+    /// an error). After a failed connect the stream must be dropped.
+    ///
+    /// [`take_error`]: TcpStream::take_error
+    pub fn connect_status(&self) -> io::Result<ConnectStatus> {
+        connect_status(self.take_error(), || self.peer_addr())
+    }
+
     /// Returns the socket address of the remote peer of this TCP connection.
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         self.inner.peer_addr()
@@ -196,6 +274,244 @@ impl TcpStream {
         self.inner.ttl()
     }
 
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// If set, the OS sends keepalive probes on an idle connection to detect
+    /// if the peer is still reachable. If the peer doesn't respond the
+    /// connection is closed, which is reported as an error event, see
+    /// [`Event::is_error`], and the error can be retrieved using
+    /// [`take_error`].
+    ///
+    /// See [`set_keepalive_time`], [`set_keepalive_interval`] and
+    /// [`set_keepalive_retries`] to configure when the probes are sent, the
+    /// defaults of most OSs only detect a dead peer after several hours.
+    ///
+    /// [`Event::is_error`]: crate::event::Event::is_error
+    /// [`take_error`]: TcpStream::take_error
+    /// [`set_keepalive_time`]: TcpStream::set_keepalive_time
+    /// [`set_keepalive_interval`]: TcpStream::set_keepalive_interval
+    /// [`set_keepalive_retries`]: TcpStream::set_keepalive_retries
+    #[cfg(unix)]
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        set_keepalive(&*self.inner, keepalive)
+    }
+
+    /// Gets the value of the `SO_KEEPALIVE` option on this socket.
+    #[cfg(unix)]
+    pub fn keepalive(&self) -> io::Result<bool> {
+        keepalive(&*self.inner)
+    }
+
+    cfg_tcp_keepalive! {
+        /// Sets the amount of time the connection must be idle before the first
+        /// keepalive probe is sent.
+        ///
+        /// This sets the `TCP_KEEPIDLE` option (`TCP_KEEPALIVE` on Apple
+        /// platforms), rounded up to whole seconds. Only has effect if
+        /// [`set_keepalive`] is enabled.
+        ///
+        /// [`set_keepalive`]: TcpStream::set_keepalive
+        pub fn set_keepalive_time(&self, time: Duration) -> io::Result<()> {
+            set_keepalive_time(&*self.inner, time)
+        }
+
+        /// Gets the idle time before the first keepalive probe is sent, see
+        /// [`set_keepalive_time`].
+        ///
+        /// [`set_keepalive_time`]: TcpStream::set_keepalive_time
+        pub fn keepalive_time(&self) -> io::Result<Duration> {
+            keepalive_time(&*self.inner)
+        }
+
+        /// Sets the time between keepalive probes, using the `TCP_KEEPINTVL`
+        /// option, rounded up to whole seconds.
+        pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+            set_keepalive_interval(&*self.inner, interval)
+        }
+
+        /// Gets the value of the `TCP_KEEPINTVL` option on this socket.
+        pub fn keepalive_interval(&self) -> io::Result<Duration> {
+            keepalive_interval(&*self.inner)
+        }
+
+        /// Sets the number of unanswered keepalive probes after which the
+        /// connection is closed, using the `TCP_KEEPCNT` option.
+        pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+            set_keepalive_retries(&*self.inner, retries)
+        }
+
+        /// Gets the value of the `TCP_KEEPCNT` option on this socket.
+        pub fn keepalive_retries(&self) -> io::Result<u32> {
+            keepalive_retries(&*self.inner)
+        }
+    }
+
+    /// Sets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// This is the maximum amount of time that transmitted data may remain
+    /// unacknowledged before the connection is closed, which is reported the
+    /// same way as a failed keepalive, see [`set_keepalive`]. This also applies
+    /// to keepalive probes, overriding [`set_keepalive_retries`]. `None` uses
+    /// the OS default. The timeout has a resolution of milliseconds.
+    ///
+    /// [`set_keepalive`]: TcpStream::set_keepalive
+    /// [`set_keepalive_retries`]: TcpStream::set_keepalive_retries
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        set_user_timeout(&*self.inner, timeout)
+    }
+
+    /// Gets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn user_timeout(&self) -> io::Result<Option<Duration>> {
+        user_timeout(&*self.inner)
+    }
+
+    /// Sets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// This controls how the socket is closed when the stream is dropped while
+    /// there is still data that has not been sent. If `None` (the default) the
+    /// close returns immediately and the OS sends the remaining data in the
+    /// background. If set to a duration the close blocks until the data is
+    /// sent or the duration has passed, with a resolution of seconds.
+    ///
+    /// If set to zero the connection is reset (RST) when the stream is dropped,
+    /// discarding any data that is not sent, see [`abort`].
+    ///
+    /// # Notes
+    ///
+    /// As the close blocks this shouldn't be set to a non-zero duration when
+    /// using the stream with non-blocking I/O.
+    ///
+    /// [`abort`]: TcpStream::abort
+    #[cfg(unix)]
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        set_linger(&*self.inner, linger)
+    }
+
+    /// Gets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// For more information about this option, see [`set_linger`].
+    ///
+    /// [`set_linger`]: TcpStream::set_linger
+    #[cfg(unix)]
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        linger(&*self.inner)
+    }
+
+    /// Abort the connection, closing the stream with a reset (RST).
+    ///
+    /// Any data that is not yet sent, or received but not read, is discarded.
+    /// The peer will see a connection reset error (`ECONNRESET`), rather than
+    /// the end of the stream it would see if the stream was closed normally.
+    /// This is done by setting `SO_LINGER` to zero before closing the socket.
+    #[cfg(unix)]
+    pub fn abort(self) -> io::Result<()> {
+        self.set_linger(Some(Duration::ZERO))?;
+        // Closing the socket sends the RST.
+        drop(self);
+        Ok(())
+    }
+
+    /// Sets the value of the `SO_RCVLOWAT` option on this socket.
+    ///
+    /// This is the minimum number of bytes that must be available in the
+    /// receive buffer before the stream is considered readable, i.e. before a
+    /// readable event is returned. Defaults to 1. A read can still return
+    /// less bytes, e.g. if the peer closed the connection.
+    #[cfg(unix)]
+    pub fn set_recv_lowat(&self, bytes: u32) -> io::Result<()> {
+        set_recv_lowat(&*self.inner, bytes)
+    }
+
+    /// Gets the value of the `SO_RCVLOWAT` option on this socket.
+    #[cfg(unix)]
+    pub fn recv_lowat(&self) -> io::Result<u32> {
+        recv_lowat(&*self.inner)
+    }
+
+    /// Sets the value of the `TCP_NOTSENT_LOWAT` option on this socket.
+    ///
+    /// The stream is only considered writable, i.e. a writable event is only
+    /// returned, once the amount of written but not yet sent data is below
+    /// `bytes`. This limits the amount of data buffered in the kernel, without
+    /// limiting the send buffer used for data that is sent but not yet
+    /// acknowledged.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_notsent_lowat(&self, bytes: u32) -> io::Result<()> {
+        set_notsent_lowat(&*self.inner, bytes)
+    }
+
+    /// Gets the value of the `TCP_NOTSENT_LOWAT` option on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn notsent_lowat(&self) -> io::Result<u32> {
+        notsent_lowat(&*self.inner)
+    }
+
+    /// Sets the congestion control algorithm used by the connection, using the
+    /// `TCP_CONGESTION` option.
+    ///
+    /// `name` is the name of the algorithm, e.g. `"cubic"` or `"reno"`. On
+    /// Linux the available algorithms are listed in
+    /// `/proc/sys/net/ipv4/tcp_available_congestion_control`, unprivileged
+    /// processes can only use the algorithms listed in
+    /// `/proc/sys/net/ipv4/tcp_allowed_congestion_control`.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_congestion(&self, name: &str) -> io::Result<()> {
+        set_congestion(&*self.inner, name)
+    }
+
+    /// Gets the name of the congestion control algorithm used by the
+    /// connection.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn congestion(&self) -> io::Result<String> {
+        congestion(&*self.inner)
+    }
+
+    /// Sets the value of the `TCP_CORK` option on this socket.
+    ///
+    /// If set, partial segments are not sent until the option is unset again
+    /// (or after 200 milliseconds). This can be used to batch multiple writes,
+    /// e.g. a header and a body, into as few segments as possible:
+    /// enable it before the writes and disable it after the last write to send
+    /// any remaining data.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_cork(&self, cork: bool) -> io::Result<()> {
+        set_cork(&*self.inner, cork)
+    }
+
+    /// Gets the value of the `TCP_CORK` option on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn cork(&self) -> io::Result<bool> {
+        cork(&*self.inner)
+    }
+
+    /// Sets the value of the `TCP_QUICKACK` option on this socket.
+    ///
+    /// If set, acknowledgements are sent immediately, rather than delayed.
+    /// Note that this option is not permanent, the OS can switch back to
+    /// delayed acknowledgements, so it may need to be set again, e.g. after
+    /// each read.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_quickack(&self, quickack: bool) -> io::Result<()> {
+        set_quickack(&*self.inner, quickack)
+    }
+
+    /// Gets the value of the `TCP_QUICKACK` option on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn quickack(&self) -> io::Result<bool> {
+        quickack(&*self.inner)
+    }
+
+    /// Returns a snapshot of the TCP state of the connection, e.g. the round
+    /// trip time and congestion window.
+    ///
+    /// This uses the `TCP_INFO` socket option.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn tcp_info(&self) -> io::Result<TcpInfo> {
+        tcp_info(&*self.inner).map(|(raw, length)| TcpInfo::new(raw, length))
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -217,6 +533,178 @@ impl TcpStream {
         self.inner.do_io(|inner| inner.peek(buf))
     }
 
+    /// Sends data on the socket using `send(2)` with `flags`. On success,
+    /// returns the number of bytes written.
+    ///
+    /// See [`MsgFlags`] for the available flags.
+    ///
+    /// [`MsgFlags`]: crate::net::MsgFlags
+    #[cfg(unix)]
+    pub fn send_with_flags(&self, buf: &[u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| send_with_flags(inner.as_raw_fd(), buf, flags))
+    }
+
+    /// Receives data on the socket using `recv(2)` with `flags`. On success,
+    /// returns the number of bytes read.
+    ///
+    /// With [`MsgFlags::TRUNC`] the data is discarded instead and the returned
+    /// length can be larger than `buf`.
+    ///
+    /// [`MsgFlags::TRUNC`]: crate::net::MsgFlags::TRUNC
+    #[cfg(unix)]
+    pub fn recv_with_flags(&self, buf: &mut [u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| recv_with_flags(inner.as_raw_fd(), buf, flags))
+    }
+
+    /// Receives data on the socket into `buf`, which doesn't have to be
+    /// initialised.
+    ///
+    /// On success, returns the number of bytes read, `n`, after which the first
+    /// `n` bytes of `buf` are initialised.
+    #[cfg(unix)]
+    pub fn recv_uninit(&self, buf: &mut [MaybeUninit<u8>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| recv_uninit(inner.as_raw_fd(), buf, MsgFlags::EMPTY))
+    }
+
+    /// Sends `buf` as out-of-band, or urgent, data (`MSG_OOB`). On success,
+    /// returns the number of bytes written.
+    ///
+    /// TCP only supports a single byte of urgent data, if `buf` is longer
+    /// only the last byte is marked as urgent, the bytes before it are sent as
+    /// normal data.
+    ///
+    /// The peer receives a [priority event] for the urgent data, which it can
+    /// read using [`recv_oob`].
+    ///
+    /// [priority event]: crate::event::Event::is_priority
+    /// [`recv_oob`]: TcpStream::recv_oob
+    #[cfg(unix)]
+    pub fn send_oob(&self, buf: &[u8]) -> io::Result<usize> {
+        self.send_with_flags(buf, MsgFlags::OOB)
+    }
+
+    /// Receives out-of-band, or urgent, data (`MSG_OOB`). On success, returns
+    /// the number of bytes read, which is at most one.
+    ///
+    /// The urgent data is available after receiving a [priority event]. Use
+    /// [`at_mark`] to determine where the urgent data was in the stream of
+    /// normal data.
+    ///
+    /// # Notes
+    ///
+    /// If no urgent data is pending, it was already read or
+    /// [`set_oobinline`] is enabled this returns an error, `EINVAL` on Linux.
+    ///
+    /// [priority event]: crate::event::Event::is_priority
+    /// [`at_mark`]: TcpStream::at_mark
+    /// [`set_oobinline`]: TcpStream::set_oobinline
+    #[cfg(unix)]
+    pub fn recv_oob(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.recv_with_flags(buf, MsgFlags::OOB)
+    }
+
+    /// Sets the value of the `SO_OOBINLINE` option on this socket.
+    ///
+    /// If enabled urgent data is received as part of the normal data, i.e.
+    /// using [`read`], instead of using [`recv_oob`].
+    ///
+    /// [`read`]: Read::read
+    /// [`recv_oob`]: TcpStream::recv_oob
+    #[cfg(unix)]
+    pub fn set_oobinline(&self, oobinline: bool) -> io::Result<()> {
+        set_oobinline(&*self.inner, oobinline)
+    }
+
+    /// Gets the value of the `SO_OOBINLINE` option on this socket.
+    #[cfg(unix)]
+    pub fn oobinline(&self) -> io::Result<bool> {
+        oobinline(&*self.inner)
+    }
+
+    /// Returns true if the read position of the stream is at the urgent data
+    /// mark (`SIOCATMARK`), i.e. all normal data sent before the urgent data
+    /// has been read.
+    ///
+    /// A read doesn't read past the mark, so after reading until this
+    /// returns true the next byte is the urgent data when [`set_oobinline`]
+    /// is enabled.
+    ///
+    /// [`set_oobinline`]: TcpStream::set_oobinline
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn at_mark(&self) -> io::Result<bool> {
+        at_mark(&*self.inner)
+    }
+
+    /// Send up to `len` bytes of `file`, starting at `offset`, using
+    /// `sendfile(2)`. This doesn't copy the data through userspace and doesn't
+    /// change the file's cursor.
+    ///
+    /// Returns the number of bytes sent, which can be less than `len`, or zero
+    /// if `offset` is at (or past) the end of the file. Like [`write`] this
+    /// returns a [`WouldBlock`] error if the socket's send buffer is full.
+    ///
+    /// [`write`]: Write::write
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn sendfile(&self, file: &File, offset: u64, len: usize) -> io::Result<usize> {
+        self.inner.do_io(|inner| sendfile(inner, file, offset, len))
+    }
+
+    /// Sets the value of the `SO_ZEROCOPY` option on this socket, required
+    /// for [`send_zerocopy`] to send without copying.
+    ///
+    /// [`send_zerocopy`]: TcpStream::send_zerocopy
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_zerocopy(&self, zerocopy: bool) -> io::Result<()> {
+        set_zerocopy(self.inner.as_raw_fd(), zerocopy)
+    }
+
+    /// Gets the value of the `SO_ZEROCOPY` option on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn zerocopy(&self) -> io::Result<bool> {
+        zerocopy(self.inner.as_raw_fd())
+    }
+
+    /// Write `buf` using `MSG_ZEROCOPY`, without copying it into the kernel.
+    ///
+    /// This requires [`set_zerocopy`] to be enabled, otherwise it's the same
+    /// as [`write`]. `buf` must not be modified until a
+    /// [`ZerocopyCompletion`] for the send is received using
+    /// [`zerocopy_completions`], see [`ZerocopyCompletion`] for how sends are
+    /// identified.
+    ///
+    /// Zerocopy generally only pays off for writes of at least 10 KB.
+    ///
+    /// [`set_zerocopy`]: TcpStream::set_zerocopy
+    /// [`write`]: Write::write
+    /// [`ZerocopyCompletion`]: crate::net::ZerocopyCompletion
+    /// [`zerocopy_completions`]: TcpStream::zerocopy_completions
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn send_zerocopy(&self, buf: &[u8]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| send_zerocopy(inner.as_raw_fd(), buf, None))
+    }
+
+    /// Returns an iterator over the completion notifications of
+    /// [`send_zerocopy`].
+    ///
+    /// The notifications are received from the socket's error queue, which is
+    /// reported as an [error event]. The iterator should be exhausted after
+    /// receiving such an event.
+    ///
+    /// This is not supported by the `poll(2)` based selector, which
+    /// deregisters the socket after an error event.
+    ///
+    /// [`send_zerocopy`]: TcpStream::send_zerocopy
+    /// [error event]: crate::event::Event::is_error
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn zerocopy_completions(&self) -> ZerocopyCompletions<'_> {
+        ZerocopyCompletions::new(self)
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -324,6 +812,15 @@ impl Write for &'_ TcpStream {
     }
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl ErrorQueue for TcpStream {
+    fn recv_zerocopy_completion(&self) -> io::Result<ZerocopyCompletion> {
+        self.inner
+            .do_io(|inner| recv_zerocopy_completion(inner.as_raw_fd()))
+            .map(|(first, last, copied)| ZerocopyCompletion::new(first, last, copied))
+    }
+}
+
 impl event::Source for TcpStream {
     fn register(
         &mut self,
@@ -408,6 +905,19 @@ impl From<OwnedFd> for TcpStream {
     }
 }
 
+#[cfg(all(unix, feature = "os-ext"))]
+impl<T: AsFd + Into<OwnedFd>> From<crate::unix::OwnedSource<T>> for TcpStream {
+    /// Converts an `OwnedSource` to a `TcpStream`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the FD is in non-blocking
+    /// mode and that it's deregistered from any `Poll` instance.
+    fn from(source: crate::unix::OwnedSource<T>) -> Self {
+        TcpStream::from(OwnedFd::from(source))
+    }
+}
+
 #[cfg(windows)]
 impl IntoRawSocket for TcpStream {
     fn into_raw_socket(self) -> RawSocket {
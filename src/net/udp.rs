@@ -12,6 +12,8 @@ use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
 // TODO: once <https://github.com/rust-lang/rust/issues/126198> is fixed this
 // can use `std::os::fd` and be merged with the above.
+#[cfg(unix)]
+use std::mem::MaybeUninit;
 #[cfg(target_os = "hermit")]
 use std::os::hermit::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
 #[cfg(windows)]
@@ -21,6 +23,12 @@ use std::os::windows::io::{
 use std::{fmt, io, net};
 
 use crate::io_source::IoSource;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::zerocopy::ErrorQueue;
+#[cfg(unix)]
+use crate::net::{MsgFlags, RecvDatagram, SendDatagram};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::{RecvControl, SendControl, ZerocopyCompletion, ZerocopyCompletions};
 use crate::{event, sys, Interest, Registry, Token};
 
 /// A User Datagram Protocol socket.
@@ -324,6 +332,119 @@ impl UdpSocket {
         self.inner.do_io(|inner| inner.peek(buf))
     }
 
+    /// Sends data on the socket to the connected address using `send(2)`
+    /// with `flags`. On success, returns the number of bytes written.
+    ///
+    /// See [`MsgFlags`] for the available flags.
+    ///
+    /// [`MsgFlags`]: crate::net::MsgFlags
+    #[cfg(unix)]
+    pub fn send_with_flags(&self, buf: &[u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::send_with_flags(inner.as_raw_fd(), buf, flags))
+    }
+
+    /// Sends data on the socket to `target` using `sendto(2)` with `flags`.
+    /// On success, returns the number of bytes written.
+    #[cfg(unix)]
+    pub fn send_to_with_flags(
+        &self,
+        buf: &[u8],
+        target: SocketAddr,
+        flags: MsgFlags,
+    ) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::send_to_with_flags(inner.as_raw_fd(), buf, target, flags))
+    }
+
+    /// Receives data from the connected address using `recv(2)` with `flags`.
+    /// On success, returns the number of bytes read.
+    ///
+    /// With [`MsgFlags::TRUNC`] the returned length is the real length of the
+    /// datagram, which can be larger than `buf`.
+    ///
+    /// [`MsgFlags::TRUNC`]: crate::net::MsgFlags::TRUNC
+    #[cfg(unix)]
+    pub fn recv_with_flags(&self, buf: &mut [u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::recv_with_flags(inner.as_raw_fd(), buf, flags))
+    }
+
+    /// Receives data from the socket using `recvfrom(2)` with `flags`. On
+    /// success, returns the number of bytes read and the address from whence
+    /// the data came.
+    ///
+    /// With [`MsgFlags::TRUNC`] the returned length is the real length of the
+    /// datagram, which can be larger than `buf`.
+    ///
+    /// [`MsgFlags::TRUNC`]: crate::net::MsgFlags::TRUNC
+    #[cfg(unix)]
+    pub fn recv_from_with_flags(
+        &self,
+        buf: &mut [u8],
+        flags: MsgFlags,
+    ) -> io::Result<(usize, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::recv_from_with_flags(inner.as_raw_fd(), buf, flags))
+    }
+
+    /// Receives data from the connected address into `buf`, which doesn't
+    /// have to be initialised.
+    ///
+    /// On success, returns the number of bytes read, `n`, after which the first
+    /// `n` bytes of `buf` are initialised.
+    #[cfg(unix)]
+    pub fn recv_uninit(&self, buf: &mut [MaybeUninit<u8>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::recv_uninit(inner.as_raw_fd(), buf, MsgFlags::EMPTY))
+    }
+
+    /// Receives data from the socket into `buf`, which doesn't have to be
+    /// initialised.
+    ///
+    /// On success, returns the number of bytes read, `n`, after which the first
+    /// `n` bytes of `buf` are initialised, and the address from whence the
+    /// data came.
+    #[cfg(unix)]
+    pub fn recv_from_uninit(&self, buf: &mut [MaybeUninit<u8>]) -> io::Result<(usize, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::recv_from_uninit(inner.as_raw_fd(), buf, MsgFlags::EMPTY))
+    }
+
+    /// Sends multiple datagrams on the socket, using a single `sendmmsg(2)`
+    /// system call where available. Each datagram is sent to its own address,
+    /// or to the connected address if it has none.
+    ///
+    /// On success, returns the number of datagrams sent, which can be less
+    /// than `msgs.len()`. An error is only returned if no datagram could be
+    /// sent, e.g. a [`WouldBlock`] error if the send buffer is full.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    #[cfg(unix)]
+    pub fn send_many(&self, msgs: &[SendDatagram<'_, SocketAddr>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::send_many(inner.as_raw_fd(), msgs))
+    }
+
+    /// Receives multiple datagrams from the socket, using a single
+    /// `recvmmsg(2)` system call where available.
+    ///
+    /// On success, returns the number of datagrams received, `n`, after which
+    /// the first `n` datagrams in `msgs` contain the received data and the
+    /// address it came from, all other datagrams in `msgs` are reset to
+    /// contain nothing. An error is only returned if no datagram could be
+    /// received, e.g. a [`WouldBlock`] error if no datagrams are queued. Like
+    /// [`recv_from`] this should be called until it returns a [`WouldBlock`]
+    /// error.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`recv_from`]: UdpSocket::recv_from
+    #[cfg(unix)]
+    pub fn recv_many(&self, msgs: &mut [RecvDatagram<'_, SocketAddr>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::recv_many(inner.as_raw_fd(), msgs))
+    }
+
     /// Connects the UDP socket setting the default destination for `send()`
     /// and limiting packets that are read via `recv` from the address specified
     /// in `addr`.
@@ -564,6 +685,261 @@ impl UdpSocket {
         self.inner.take_error()
     }
 
+    /// Sends `buf` to the connected address as multiple datagrams of
+    /// `segment_size` bytes using generic segmentation offload
+    /// (`UDP_SEGMENT`), only the last datagram can be smaller.
+    ///
+    /// This allows sending many datagrams in a single system call, the
+    /// datagrams are split by the kernel, or by the network card if it
+    /// supports it. On success, returns the number of bytes sent, which is all
+    /// of `buf`.
+    ///
+    /// # Notes
+    ///
+    /// `buf` can't be split into more than 64 datagrams and each datagram
+    /// must fit in the MTU, otherwise this returns an `EINVAL` error.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn send_segmented(&self, buf: &[u8], segment_size: u16) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_segmented(inner, buf, None, segment_size))
+    }
+
+    /// Same as [`send_segmented`], but sends to `target`.
+    ///
+    /// [`send_segmented`]: UdpSocket::send_segmented
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn send_to_segmented(
+        &self,
+        buf: &[u8],
+        target: SocketAddr,
+        segment_size: u16,
+    ) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_segmented(inner, buf, Some(target), segment_size))
+    }
+
+    /// Sets the value of the `UDP_GRO` option on this socket.
+    ///
+    /// If enabled the kernel can coalesce multiple datagrams from the same
+    /// address into a single buffer (generic receive offload), which can be
+    /// received using [`recv_from_segmented`].
+    ///
+    /// [`recv_from_segmented`]: UdpSocket::recv_from_segmented
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_gro(&self, gro: bool) -> io::Result<()> {
+        sys::udp::set_gro(&self.inner, gro)
+    }
+
+    /// Gets the value of the `UDP_GRO` option on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn gro(&self) -> io::Result<bool> {
+        sys::udp::gro(&self.inner)
+    }
+
+    /// Receives data from the socket, which can be multiple coalesced
+    /// datagrams if [`set_gro`] is enabled.
+    ///
+    /// On success, returns the number of bytes read, the address from whence
+    /// the data came and the segment size if multiple datagrams were
+    /// coalesced. In that case `buf` contains consecutive datagrams of the
+    /// segment size, only the last one can be smaller. `buf` should be large
+    /// enough to hold the coalesced datagrams, i.e. 64 KB, otherwise the data
+    /// is truncated.
+    ///
+    /// [`set_gro`]: UdpSocket::set_gro
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_from_segmented(
+        &self,
+        buf: &mut [u8],
+    ) -> io::Result<(usize, SocketAddr, Option<usize>)> {
+        self.inner
+            .do_io(|inner| sys::udp::recv_from_segmented(inner, buf))
+    }
+
+    /// Sets the value of the `IP_PKTINFO` option, and `IPV6_RECVPKTINFO` for
+    /// IPv6 sockets, on this socket.
+    ///
+    /// If enabled [`recv_msg`] receives a [`ControlMessage::PacketInfo`] with
+    /// the destination address of the datagram and the interface it was
+    /// received on. This is useful for servers bound to an unspecified
+    /// address on a multi-homed host, which can use it to reply from the
+    /// address the request was sent to using [`send_to_msg`].
+    ///
+    /// [`recv_msg`]: UdpSocket::recv_msg
+    /// [`send_to_msg`]: UdpSocket::send_to_msg
+    /// [`ControlMessage::PacketInfo`]: crate::net::ControlMessage::PacketInfo
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_recv_pktinfo(&self, enable: bool) -> io::Result<()> {
+        sys::udp::set_recv_pktinfo(&self.inner, enable)
+    }
+
+    /// Gets the value of the `IP_PKTINFO` option, or `IPV6_RECVPKTINFO` for
+    /// IPv6 sockets, on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_pktinfo(&self) -> io::Result<bool> {
+        sys::udp::recv_pktinfo(&self.inner)
+    }
+
+    /// Sets the value of the `IP_RECVTTL` option, and `IPV6_RECVHOPLIMIT` for
+    /// IPv6 sockets, on this socket.
+    ///
+    /// If enabled [`recv_msg`] receives a [`ControlMessage::Ttl`] or
+    /// [`ControlMessage::HopLimit`] with the TTL or hop limit of the datagram.
+    ///
+    /// [`recv_msg`]: UdpSocket::recv_msg
+    /// [`ControlMessage::Ttl`]: crate::net::ControlMessage::Ttl
+    /// [`ControlMessage::HopLimit`]: crate::net::ControlMessage::HopLimit
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_recv_ttl(&self, enable: bool) -> io::Result<()> {
+        sys::udp::set_recv_ttl(&self.inner, enable)
+    }
+
+    /// Gets the value of the `IP_RECVTTL` option, or `IPV6_RECVHOPLIMIT` for
+    /// IPv6 sockets, on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_ttl(&self) -> io::Result<bool> {
+        sys::udp::recv_ttl(&self.inner)
+    }
+
+    /// Sets the value of the `SO_TIMESTAMPNS` option on this socket.
+    ///
+    /// If enabled [`recv_msg`] receives a [`ControlMessage::Timestamp`] with
+    /// the time the datagram was received by the kernel.
+    ///
+    /// [`recv_msg`]: UdpSocket::recv_msg
+    /// [`ControlMessage::Timestamp`]: crate::net::ControlMessage::Timestamp
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_recv_timestamp(&self, enable: bool) -> io::Result<()> {
+        sys::udp::set_recv_timestamp(&self.inner, enable)
+    }
+
+    /// Gets the value of the `SO_TIMESTAMPNS` option on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_timestamp(&self) -> io::Result<bool> {
+        sys::udp::recv_timestamp(&self.inner)
+    }
+
+    /// Sets the value of the `SO_RXQ_OVFL` option on this socket.
+    ///
+    /// If enabled [`recv_msg`] receives a [`ControlMessage::Overflow`] with
+    /// the number of datagrams the socket dropped so far, if any.
+    ///
+    /// [`recv_msg`]: UdpSocket::recv_msg
+    /// [`ControlMessage::Overflow`]: crate::net::ControlMessage::Overflow
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_recv_overflow(&self, enable: bool) -> io::Result<()> {
+        sys::udp::set_recv_overflow(&self.inner, enable)
+    }
+
+    /// Gets the value of the `SO_RXQ_OVFL` option on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_overflow(&self) -> io::Result<bool> {
+        sys::udp::recv_overflow(&self.inner)
+    }
+
+    /// Sends data on the socket to the connected address along with the
+    /// control messages in `control`, e.g. to set the source address of the
+    /// datagram. On success, returns the number of bytes written.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn send_msg(&self, buf: &[u8], control: &SendControl) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_msg(inner, buf, None, control.as_bytes()))
+    }
+
+    /// Same as [`send_msg`], but sends to `target`.
+    ///
+    /// [`send_msg`]: UdpSocket::send_msg
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn send_to_msg(
+        &self,
+        buf: &[u8],
+        target: SocketAddr,
+        control: &SendControl,
+    ) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::udp::send_msg(inner, buf, Some(target), control.as_bytes()))
+    }
+
+    /// Receives data from the socket along with its control messages, which
+    /// are stored in `control`. On success, returns the number of bytes read
+    /// and the address from whence the data came.
+    ///
+    /// Which control messages are received depends on the options set on
+    /// this socket, e.g. [`set_recv_pktinfo`].
+    ///
+    /// [`set_recv_pktinfo`]: UdpSocket::set_recv_pktinfo
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_msg(
+        &self,
+        buf: &mut [u8],
+        control: &mut RecvControl,
+    ) -> io::Result<(usize, SocketAddr)> {
+        let (n, addr, control_len, truncated) = self
+            .inner
+            .do_io(|inner| sys::udp::recv_msg(inner, buf, control.buf_mut()))?;
+        control.set_received(control_len, truncated);
+        Ok((n, addr))
+    }
+
+    /// Sets the value of the `SO_ZEROCOPY` option on this socket, required
+    /// for [`send_zerocopy`] to send without copying.
+    ///
+    /// [`send_zerocopy`]: UdpSocket::send_zerocopy
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_zerocopy(&self, zerocopy: bool) -> io::Result<()> {
+        sys::set_zerocopy(self.inner.as_raw_fd(), zerocopy)
+    }
+
+    /// Gets the value of the `SO_ZEROCOPY` option on this socket.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn zerocopy(&self) -> io::Result<bool> {
+        sys::zerocopy(self.inner.as_raw_fd())
+    }
+
+    /// Sends data on the socket to the connected address using
+    /// `MSG_ZEROCOPY`, without copying `buf` into the kernel.
+    ///
+    /// This requires [`set_zerocopy`] to be enabled. `buf` must not be
+    /// modified until a [`ZerocopyCompletion`] for the send is received using
+    /// [`zerocopy_completions`], see [`ZerocopyCompletion`] for how sends are
+    /// identified.
+    ///
+    /// [`set_zerocopy`]: UdpSocket::set_zerocopy
+    /// [`ZerocopyCompletion`]: crate::net::ZerocopyCompletion
+    /// [`zerocopy_completions`]: UdpSocket::zerocopy_completions
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn send_zerocopy(&self, buf: &[u8]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::send_zerocopy(inner.as_raw_fd(), buf, None))
+    }
+
+    /// Same as [`send_zerocopy`], but sends to `target`.
+    ///
+    /// [`send_zerocopy`]: UdpSocket::send_zerocopy
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn send_to_zerocopy(&self, buf: &[u8], target: SocketAddr) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::send_zerocopy(inner.as_raw_fd(), buf, Some(target)))
+    }
+
+    /// Returns an iterator over the completion notifications of
+    /// [`send_zerocopy`] and [`send_to_zerocopy`].
+    ///
+    /// The notifications are received from the socket's error queue, which is
+    /// reported as an [error event]. The iterator should be exhausted after
+    /// receiving such an event.
+    ///
+    /// This is not supported by the `poll(2)` based selector, which
+    /// deregisters the socket after an error event.
+    ///
+    /// [`send_zerocopy`]: UdpSocket::send_zerocopy
+    /// [`send_to_zerocopy`]: UdpSocket::send_to_zerocopy
+    /// [error event]: crate::event::Event::is_error
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn zerocopy_completions(&self) -> ZerocopyCompletions<'_> {
+        ZerocopyCompletions::new(self)
+    }
+
     /// Execute an I/O operation ensuring that the socket receives more events
     /// if it hits a [`WouldBlock`] error.
     ///
@@ -623,6 +999,15 @@ impl UdpSocket {
     }
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl ErrorQueue for UdpSocket {
+    fn recv_zerocopy_completion(&self) -> io::Result<ZerocopyCompletion> {
+        self.inner
+            .do_io(|inner| sys::recv_zerocopy_completion(inner.as_raw_fd()))
+            .map(|(first, last, copied)| ZerocopyCompletion::new(first, last, copied))
+    }
+}
+
 impl event::Source for UdpSocket {
     fn register(
         &mut self,
@@ -707,6 +1092,19 @@ impl From<OwnedFd> for UdpSocket {
     }
 }
 
+#[cfg(all(unix, feature = "os-ext"))]
+impl<T: AsFd + Into<OwnedFd>> From<crate::unix::OwnedSource<T>> for UdpSocket {
+    /// Converts an `OwnedSource` to a `UdpSocket`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the FD is in non-blocking
+    /// mode and that it's deregistered from any `Poll` instance.
+    fn from(source: crate::unix::OwnedSource<T>) -> Self {
+        UdpSocket::from(OwnedFd::from(source))
+    }
+}
+
 #[cfg(windows)]
 impl IntoRawSocket for UdpSocket {
     fn into_raw_socket(self) -> RawSocket {
@@ -1,3 +1,4 @@
+use std::mem::MaybeUninit;
 use std::net::Shutdown;
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
 use std::os::unix::net::{self, SocketAddr};
@@ -5,6 +6,7 @@ use std::path::Path;
 use std::{fmt, io};
 
 use crate::io_source::IoSource;
+use crate::net::{MsgFlags, RecvDatagram, SendDatagram};
 use crate::{event, sys, Interest, Registry, Token};
 
 /// A Unix datagram socket.
@@ -103,6 +105,107 @@ impl UnixDatagram {
         self.inner.do_io(|inner| inner.send(buf))
     }
 
+    /// Receives data from the socket using `recv(2)` with `flags`.
+    ///
+    /// On success, returns the number of bytes read. With
+    /// [`MsgFlags::TRUNC`] this is the real length of the datagram, which can
+    /// be larger than `buf`.
+    pub fn recv_with_flags(&self, buf: &mut [u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::recv_with_flags(inner.as_raw_fd(), buf, flags))
+    }
+
+    /// Receives data from the socket using `recvfrom(2)` with `flags`.
+    ///
+    /// On success, returns the number of bytes read and the address from
+    /// whence the data came. With [`MsgFlags::TRUNC`] the number of bytes is
+    /// the real length of the datagram, which can be larger than `buf`.
+    pub fn recv_from_with_flags(
+        &self,
+        buf: &mut [u8],
+        flags: MsgFlags,
+    ) -> io::Result<(usize, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_from_with_flags(inner, buf, flags))
+    }
+
+    /// Receives data from the socket into `buf`, which doesn't have to be
+    /// initialised.
+    ///
+    /// On success, returns the number of bytes read, `n`, after which the
+    /// first `n` bytes of `buf` are initialised.
+    pub fn recv_uninit(&self, buf: &mut [MaybeUninit<u8>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::recv_uninit(inner.as_raw_fd(), buf, MsgFlags::EMPTY))
+    }
+
+    /// Receives data from the socket into `buf`, which doesn't have to be
+    /// initialised.
+    ///
+    /// On success, returns the number of bytes read, `n`, after which the
+    /// first `n` bytes of `buf` are initialised, and the address from whence
+    /// the data came.
+    pub fn recv_from_uninit(&self, buf: &mut [MaybeUninit<u8>]) -> io::Result<(usize, SocketAddr)> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_from_uninit(inner, buf, MsgFlags::EMPTY))
+    }
+
+    /// Sends multiple datagrams on the socket, using a single `sendmmsg(2)`
+    /// system call where available. Each datagram is sent to its own address,
+    /// or to the socket's peer if it has none.
+    ///
+    /// On success, returns the number of datagrams sent, which can be less
+    /// than `msgs.len()`. An error is only returned if no datagram could be
+    /// sent, e.g. a [`WouldBlock`] error if the send buffer is full.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    pub fn send_many(&self, msgs: &[SendDatagram<'_, SocketAddr>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::send_many(inner, msgs))
+    }
+
+    /// Receives multiple datagrams from the socket, using a single
+    /// `recvmmsg(2)` system call where available.
+    ///
+    /// On success, returns the number of datagrams received, `n`, after which
+    /// the first `n` datagrams in `msgs` contain the received data and the
+    /// address it came from, all other datagrams in `msgs` are reset to
+    /// contain nothing. An error is only returned if no datagram could be
+    /// received, e.g. a [`WouldBlock`] error if no datagrams are queued. Like
+    /// [`recv_from`] this should be called until it returns a [`WouldBlock`]
+    /// error.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`recv_from`]: UnixDatagram::recv_from
+    pub fn recv_many(&self, msgs: &mut [RecvDatagram<'_, SocketAddr>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::uds::datagram::recv_many(inner, msgs))
+    }
+
+    /// Sends data on the socket to the socket's peer using `send(2)` with
+    /// `flags`.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send_with_flags(&self, buf: &[u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::send_with_flags(inner.as_raw_fd(), buf, flags))
+    }
+
+    /// Sends data on the socket to the specified address using `sendto(2)`
+    /// with `flags`.
+    ///
+    /// On success, returns the number of bytes written.
+    pub fn send_to_with_flags<P: AsRef<Path>>(
+        &self,
+        buf: &[u8],
+        path: P,
+        flags: MsgFlags,
+    ) -> io::Result<usize> {
+        let address = SocketAddr::from_pathname(path)?;
+        self.inner
+            .do_io(|inner| sys::uds::datagram::send_to_with_flags(inner, buf, &address, flags))
+    }
+
     /// Returns the value of the `SO_ERROR` option.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
@@ -267,3 +370,16 @@ impl From<OwnedFd> for UnixDatagram {
         UnixDatagram::from_std(From::from(fd))
     }
 }
+
+#[cfg(all(unix, feature = "os-ext"))]
+impl<T: AsFd + Into<OwnedFd>> From<crate::unix::OwnedSource<T>> for UnixDatagram {
+    /// Converts an `OwnedSource` to a `UnixDatagram`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the FD is in non-blocking
+    /// mode and that it's deregistered from any `Poll` instance.
+    fn from(source: crate::unix::OwnedSource<T>) -> Self {
+        UnixDatagram::from(OwnedFd::from(source))
+    }
+}
@@ -4,7 +4,8 @@ use std::path::Path;
 use std::{fmt, io};
 
 use crate::io_source::IoSource;
-use crate::net::UnixStream;
+use crate::net::accept::accept_many;
+use crate::net::{AcceptReserve, AcceptStop, Accepted, UnixStream};
 use crate::{event, sys, Interest, Registry, Token};
 
 /// A non-blocking Unix domain socket server.
@@ -44,6 +45,33 @@ impl UnixListener {
         sys::uds::listener::accept(&self.inner)
     }
 
+    /// Accepts up to `max` new incoming connections, adding them to
+    /// `streams`.
+    ///
+    /// See [`TcpListener::accept_many`] for details.
+    ///
+    /// [`TcpListener::accept_many`]: crate::net::TcpListener::accept_many
+    pub fn accept_many(
+        &self,
+        streams: &mut Vec<(UnixStream, SocketAddr)>,
+        max: usize,
+    ) -> io::Result<AcceptStop> {
+        accept_many(streams, max, || self.accept())
+    }
+
+    /// Accepts a new incoming connection, shedding the connection if the
+    /// process ran out of file descriptors.
+    ///
+    /// See [`TcpListener::accept_reserved`] for details.
+    ///
+    /// [`TcpListener::accept_reserved`]: crate::net::TcpListener::accept_reserved
+    pub fn accept_reserved(
+        &self,
+        reserve: &mut AcceptReserve,
+    ) -> io::Result<Accepted<UnixStream, SocketAddr>> {
+        reserve.accept(|| self.accept())
+    }
+
     /// Returns the local socket address of this listener.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         self.inner.local_addr()
@@ -135,3 +163,16 @@ impl From<OwnedFd> for UnixListener {
         UnixListener::from_std(From::from(fd))
     }
 }
+
+#[cfg(all(unix, feature = "os-ext"))]
+impl<T: AsFd + Into<OwnedFd>> From<crate::unix::OwnedSource<T>> for UnixListener {
+    /// Converts an `OwnedSource` to a `UnixListener`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the FD is in non-blocking
+    /// mode and that it's deregistered from any `Poll` instance.
+    fn from(source: crate::unix::OwnedSource<T>) -> Self {
+        UnixListener::from(OwnedFd::from(source))
+    }
+}
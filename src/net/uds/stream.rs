@@ -1,11 +1,14 @@
 use std::fmt;
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
+use std::mem::MaybeUninit;
 use std::net::Shutdown;
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
 use std::os::unix::net::{self, SocketAddr};
 use std::path::Path;
 
 use crate::io_source::IoSource;
+use crate::net::connect::connect_status;
+use crate::net::{ConnectStatus, MsgFlags};
 use crate::{event, sys, Interest, Registry, Token};
 
 /// A non-blocking Unix stream socket.
@@ -18,6 +21,10 @@ impl UnixStream {
     ///
     /// This may return a `WouldBlock` in which case the socket connection
     /// cannot be completed immediately. Usually it means the backlog is full.
+    ///
+    /// The connection might not be established once this returns, use
+    /// [`UnixStream::connect_status`] after receiving a writable event to
+    /// determine if it is.
     pub fn connect<P: AsRef<Path>>(path: P) -> io::Result<UnixStream> {
         let addr = SocketAddr::from_pathname(path)?;
         UnixStream::connect_addr(&addr)
@@ -58,6 +65,18 @@ impl UnixStream {
         })
     }
 
+    /// Returns the status of a non-blocking connect, e.g. one started by
+    /// [`UnixStream::connect`].
+    ///
+    /// [`ConnectStatus::Failed`] is returned only once, after a failed connect
+    /// the stream must be dropped. See [`TcpStream::connect_status`] for more
+    /// information.
+    ///
+    /// [`TcpStream::connect_status`]: crate::net::TcpStream::connect_status
+    pub fn connect_status(&self) -> io::Result<ConnectStatus> {
+        connect_status(self.take_error(), || self.peer_addr())
+    }
+
     /// Returns the socket address of the local half of this connection.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         self.inner.local_addr()
@@ -73,6 +92,53 @@ impl UnixStream {
         self.inner.take_error()
     }
 
+    /// Sets the value of the `SO_RCVLOWAT` option on this socket.
+    ///
+    /// This is the minimum number of bytes that must be available in the
+    /// receive buffer before the stream is considered readable, i.e. before a
+    /// readable event is returned. Defaults to 1. A read can still return
+    /// less bytes, e.g. if the peer closed the connection.
+    ///
+    /// # Notes
+    ///
+    /// Linux and Android accept this option for Unix sockets, but don't take it
+    /// into account when determining readiness, i.e. a readable event is
+    /// returned as soon as any data is available.
+    pub fn set_recv_lowat(&self, bytes: u32) -> io::Result<()> {
+        sys::uds::stream::set_recv_lowat(&self.inner, bytes)
+    }
+
+    /// Gets the value of the `SO_RCVLOWAT` option on this socket.
+    pub fn recv_lowat(&self) -> io::Result<u32> {
+        sys::uds::stream::recv_lowat(&self.inner)
+    }
+
+    /// Sends data on the socket using `send(2)` with `flags`. On success,
+    /// returns the number of bytes written.
+    ///
+    /// See [`MsgFlags`] for the available flags.
+    pub fn send_with_flags(&self, buf: &[u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::send_with_flags(inner.as_raw_fd(), buf, flags))
+    }
+
+    /// Receives data on the socket using `recv(2)` with `flags`. On success,
+    /// returns the number of bytes read.
+    pub fn recv_with_flags(&self, buf: &mut [u8], flags: MsgFlags) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::recv_with_flags(inner.as_raw_fd(), buf, flags))
+    }
+
+    /// Receives data on the socket into `buf`, which doesn't have to be
+    /// initialised.
+    ///
+    /// On success, returns the number of bytes read, `n`, after which the first
+    /// `n` bytes of `buf` are initialised.
+    pub fn recv_uninit(&self, buf: &mut [MaybeUninit<u8>]) -> io::Result<usize> {
+        self.inner
+            .do_io(|inner| sys::recv_uninit(inner.as_raw_fd(), buf, MsgFlags::EMPTY))
+    }
+
     /// Shuts down the read, write, or both halves of this connection.
     ///
     /// This function will cause all pending and future I/O calls on the
@@ -280,3 +346,16 @@ impl From<OwnedFd> for UnixStream {
         UnixStream::from_std(From::from(fd))
     }
 }
+
+#[cfg(all(unix, feature = "os-ext"))]
+impl<T: AsFd + Into<OwnedFd>> From<crate::unix::OwnedSource<T>> for UnixStream {
+    /// Converts an `OwnedSource` to a `UnixStream`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the FD is in non-blocking
+    /// mode and that it's deregistered from any `Poll` instance.
+    fn from(source: crate::unix::OwnedSource<T>) -> Self {
+        UnixStream::from(OwnedFd::from(source))
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+use std::io;
+
+/// Completion notification of `MSG_ZEROCOPY` sends, see
+/// [`TcpStream::send_zerocopy`] and [`UdpSocket::send_zerocopy`].
+///
+/// Each successful zerocopy send on a socket is assigned a sequence number,
+/// starting at zero for the first send and incremented for every send after
+/// it. A completion covers the range of sequence numbers from [`first`] to
+/// [`last`] (inclusive), once received the buffers of those sends may be reused.
+/// Note that the sequence numbers are 32 bit and wrap around.
+///
+/// [`TcpStream::send_zerocopy`]: crate::net::TcpStream::send_zerocopy
+/// [`UdpSocket::send_zerocopy`]: crate::net::UdpSocket::send_zerocopy
+/// [`first`]: ZerocopyCompletion::first
+/// [`last`]: ZerocopyCompletion::last
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ZerocopyCompletion {
+    first: u32,
+    last: u32,
+    copied: bool,
+}
+
+impl ZerocopyCompletion {
+    pub(crate) const fn new(first: u32, last: u32, copied: bool) -> ZerocopyCompletion {
+        ZerocopyCompletion {
+            first,
+            last,
+            copied,
+        }
+    }
+
+    /// Sequence number of the first completed send.
+    pub const fn first(&self) -> u32 {
+        self.first
+    }
+
+    /// Sequence number of the last completed send.
+    pub const fn last(&self) -> u32 {
+        self.last
+    }
+
+    /// Number of completed sends.
+    pub const fn count(&self) -> u64 {
+        self.last.wrapping_sub(self.first) as u64 + 1
+    }
+
+    /// Returns true if `sequence` is part of this completion.
+    pub const fn contains(&self, sequence: u32) -> bool {
+        sequence.wrapping_sub(self.first) <= self.last.wrapping_sub(self.first)
+    }
+
+    /// Returns true if the kernel copied the data instead of sending it
+    /// without copying, e.g. for sends over loopback. If this is true for most
+    /// completions zerocopy is only overhead and should be disabled.
+    pub const fn is_copied(&self) -> bool {
+        self.copied
+    }
+}
+
+/// Iterator over the [`ZerocopyCompletion`]s of a socket, see
+/// [`TcpStream::zerocopy_completions`] and
+/// [`UdpSocket::zerocopy_completions`].
+///
+/// Each iteration receives a notification from the error queue of the socket.
+/// The iterator ends once the error queue is empty. Errors in the error queue
+/// not related to zerocopy, such as ICMP errors, are returned as error.
+///
+/// [`TcpStream::zerocopy_completions`]: crate::net::TcpStream::zerocopy_completions
+/// [`UdpSocket::zerocopy_completions`]: crate::net::UdpSocket::zerocopy_completions
+pub struct ZerocopyCompletions<'a> {
+    socket: &'a dyn ErrorQueue,
+}
+
+/// Socket with an error queue containing zerocopy completions.
+pub(crate) trait ErrorQueue {
+    /// Receive a completion, ensuring the socket receives more events if it
+    /// returns a `WouldBlock` error.
+    fn recv_zerocopy_completion(&self) -> io::Result<ZerocopyCompletion>;
+}
+
+impl<'a> ZerocopyCompletions<'a> {
+    pub(crate) fn new(socket: &'a dyn ErrorQueue) -> ZerocopyCompletions<'a> {
+        ZerocopyCompletions { socket }
+    }
+}
+
+impl Iterator for ZerocopyCompletions<'_> {
+    type Item = io::Result<ZerocopyCompletion>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.socket.recv_zerocopy_completion() {
+            Ok(completion) => Some(Ok(completion)),
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+impl fmt::Debug for ZerocopyCompletions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZerocopyCompletions").finish()
+    }
+}
@@ -18,11 +18,16 @@
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, RawFd};
 #[cfg(all(debug_assertions, not(any(target_os = "wasi", target_os = "horizon"))))]
 use std::sync::atomic::{AtomicBool, Ordering};
-#[cfg(all(debug_assertions, not(any(target_os = "wasi", target_os = "horizon"))))]
+#[cfg(any(
+    all(debug_assertions, not(any(target_os = "wasi", target_os = "horizon"))),
+    all(unix, feature = "test-util")
+))]
 use std::sync::Arc;
 use std::time::Duration;
 use std::{fmt, io};
 
+#[cfg(all(unix, feature = "test-util"))]
+use crate::test_util::{FaultState, MockState};
 use crate::{event, sys, Events, Interest, Token};
 
 /// Polls for readiness events on all registered values.
@@ -278,6 +283,21 @@ pub struct Registry {
     /// Whether this selector currently has an associated waker.
     #[cfg(all(debug_assertions, not(any(target_os = "wasi", target_os = "horizon"))))]
     has_waker: Arc<AtomicBool>,
+    /// Whether this is a mock `Poll` or one injecting faults.
+    #[cfg(all(unix, feature = "test-util"))]
+    test_state: TestState,
+}
+
+/// Testing state of a `Registry`, see [`crate::test_util`].
+#[cfg(all(unix, feature = "test-util"))]
+#[derive(Clone)]
+enum TestState {
+    /// A regular `Poll`.
+    None,
+    /// A mock `Poll`, see [`crate::test_util::Mock`].
+    Mock(Arc<MockState>),
+    /// A `Poll` injecting faults, see [`crate::test_util::Faults`].
+    Faults(Arc<FaultState>),
 }
 
 impl Poll {
@@ -328,11 +348,30 @@ impl Poll {
                     selector,
                     #[cfg(all(debug_assertions, not(any(target_os = "wasi", target_os = "horizon"))))]
                     has_waker: Arc::new(AtomicBool::new(false)),
+                    #[cfg(all(unix, feature = "test-util"))]
+                    test_state: TestState::None,
                 },
             })
         }
     }
 
+    /// Create a new mock `Poll`, see [`crate::test_util::Mock`].
+    #[cfg(all(unix, feature = "test-util"))]
+    pub(crate) fn new_mock(mock: Arc<MockState>) -> io::Result<Poll> {
+        let mut poll = Poll::new()?;
+        poll.registry.test_state = TestState::Mock(mock);
+        Ok(poll)
+    }
+
+    /// Create a new `Poll` that injects faults, see
+    /// [`crate::test_util::Faults`].
+    #[cfg(all(unix, feature = "test-util"))]
+    pub(crate) fn new_faults(faults: Arc<FaultState>) -> io::Result<Poll> {
+        let mut poll = Poll::new()?;
+        poll.registry.test_state = TestState::Faults(faults);
+        Ok(poll)
+    }
+
     /// Returns a `Registry` which can be used to register
     /// `event::Source`s.
     pub fn registry(&self) -> &Registry {
@@ -440,6 +479,15 @@ impl Poll {
     ///
     /// [struct]: #
     pub fn poll(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        #[cfg(all(unix, feature = "test-util"))]
+        match &self.registry.test_state {
+            TestState::None => {}
+            TestState::Mock(mock) => return mock.select(events.sys(), timeout),
+            TestState::Faults(faults) => {
+                let selector = &self.registry.selector;
+                return faults.select(events.sys(), |events| selector.select(events, timeout));
+            }
+        }
         self.registry.selector.select(events.sys(), timeout)
     }
 }
@@ -721,6 +769,8 @@ impl Registry {
             selector,
             #[cfg(all(debug_assertions, not(any(target_os = "wasi", target_os = "horizon"))))]
             has_waker: Arc::clone(&self.has_waker),
+            #[cfg(all(unix, feature = "test-util"))]
+            test_state: self.test_state.clone(),
         })
     }
 
@@ -740,6 +790,45 @@ impl Registry {
     pub(crate) fn selector(&self) -> &sys::Selector {
         &self.selector
     }
+
+    /// Get access to the `sys::Selector` to (re|de)register file descriptors.
+    #[cfg(all(
+        any(
+            unix,
+            target_os = "hermit",
+            all(target_os = "wasi", not(target_env = "p1"))
+        ),
+        not(all(unix, feature = "test-util"))
+    ))]
+    #[cfg_attr(
+        not(any(feature = "os-ext", all(feature = "os-poll", feature = "net"))),
+        allow(dead_code)
+    )]
+    pub(crate) fn fault_selector(&self) -> &sys::Selector {
+        &self.selector
+    }
+
+    /// Get access to the `sys::Selector` to (re|de)register file descriptors,
+    /// injecting faults into these operations if enabled.
+    #[cfg(all(unix, feature = "test-util"))]
+    #[cfg_attr(not(any(feature = "net", feature = "os-ext")), allow(dead_code))]
+    pub(crate) fn fault_selector(&self) -> crate::test_util::Selector<'_> {
+        let faults = match &self.test_state {
+            TestState::Faults(faults) => Some(&**faults),
+            TestState::None | TestState::Mock(_) => None,
+        };
+        crate::test_util::Selector::new(&self.selector, faults)
+    }
+
+    /// Get access to the state of the mock `Poll`, if this is one.
+    #[cfg(all(unix, feature = "test-util"))]
+    #[cfg_attr(not(any(feature = "net", feature = "os-ext")), allow(dead_code))]
+    pub(crate) fn mock(&self) -> Option<&MockState> {
+        match &self.test_state {
+            TestState::Mock(mock) => Some(mock),
+            TestState::None | TestState::Faults(_) => None,
+        }
+    }
 }
 
 impl fmt::Debug for Registry {
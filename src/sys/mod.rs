@@ -81,6 +81,6 @@ cfg_not_os_poll! {
     cfg_any_os_ext! {
         mod unix;
         #[cfg(feature = "os-ext")]
-        pub use self::unix::SourceFd;
+        pub use self::unix::{BorrowedSource, OwnedSource, SourceFd};
     }
 }
@@ -17,6 +17,108 @@ cfg_net! {
     pub(crate) mod udp;
     #[cfg(unix)]
     pub(crate) mod uds;
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub(crate) fn steer_reuseport_by_cpu(_: std::os::fd::RawFd, _: u32) -> std::io::Result<()> {
+        os_required!();
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub(crate) fn set_zerocopy(_: std::os::fd::RawFd, _: bool) -> std::io::Result<()> {
+        os_required!();
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub(crate) fn zerocopy(_: std::os::fd::RawFd) -> std::io::Result<bool> {
+        os_required!();
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub(crate) fn send_zerocopy(
+        _: std::os::fd::RawFd,
+        _: &[u8],
+        _: Option<std::net::SocketAddr>,
+    ) -> std::io::Result<usize> {
+        os_required!();
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub(crate) fn recv_zerocopy_completion(
+        _: std::os::fd::RawFd,
+    ) -> std::io::Result<(u32, u32, bool)> {
+        os_required!();
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn send_with_flags(
+        _: std::os::fd::RawFd,
+        _: &[u8],
+        _: crate::net::MsgFlags,
+    ) -> std::io::Result<usize> {
+        os_required!();
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn send_to_with_flags(
+        _: std::os::fd::RawFd,
+        _: &[u8],
+        _: std::net::SocketAddr,
+        _: crate::net::MsgFlags,
+    ) -> std::io::Result<usize> {
+        os_required!();
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn recv_with_flags(
+        _: std::os::fd::RawFd,
+        _: &mut [u8],
+        _: crate::net::MsgFlags,
+    ) -> std::io::Result<usize> {
+        os_required!();
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn send_many(
+        _: std::os::fd::RawFd,
+        _: &[crate::net::SendDatagram<'_, std::net::SocketAddr>],
+    ) -> std::io::Result<usize> {
+        os_required!();
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn recv_many(
+        _: std::os::fd::RawFd,
+        _: &mut [crate::net::RecvDatagram<'_, std::net::SocketAddr>],
+    ) -> std::io::Result<usize> {
+        os_required!();
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn recv_uninit(
+        _: std::os::fd::RawFd,
+        _: &mut [std::mem::MaybeUninit<u8>],
+        _: crate::net::MsgFlags,
+    ) -> std::io::Result<usize> {
+        os_required!();
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn recv_from_uninit(
+        _: std::os::fd::RawFd,
+        _: &mut [std::mem::MaybeUninit<u8>],
+        _: crate::net::MsgFlags,
+    ) -> std::io::Result<(usize, std::net::SocketAddr)> {
+        os_required!();
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn recv_from_with_flags(
+        _: std::os::fd::RawFd,
+        _: &mut [u8],
+        _: crate::net::MsgFlags,
+    ) -> std::io::Result<(usize, std::net::SocketAddr)> {
+        os_required!();
+    }
 }
 
 cfg_io_source! {
@@ -1,5 +1,7 @@
 use std::io;
 use std::net::{self, SocketAddr};
+#[cfg(unix)]
+use std::time::Duration;
 
 #[cfg(not(target_os = "wasi"))]
 pub(crate) fn new_for_addr(_: SocketAddr) -> io::Result<i32> {
@@ -7,22 +9,228 @@ pub(crate) fn new_for_addr(_: SocketAddr) -> io::Result<i32> {
 }
 
 #[cfg(not(target_os = "wasi"))]
-pub(crate) fn bind(_: &net::TcpListener, _: SocketAddr) -> io::Result<()> {
+pub(crate) fn bind<S>(_: &S, _: SocketAddr) -> io::Result<()> {
     os_required!();
 }
 
 #[cfg(not(target_os = "wasi"))]
-pub(crate) fn connect(_: &net::TcpStream, _: SocketAddr) -> io::Result<()> {
+pub(crate) fn connect<S>(_: &S, _: SocketAddr) -> io::Result<()> {
     os_required!();
 }
 
 #[cfg(not(target_os = "wasi"))]
-pub(crate) fn listen(_: &net::TcpListener, _: i32) -> io::Result<()> {
+pub(crate) fn listen<S>(_: &S, _: i32) -> io::Result<()> {
     os_required!();
 }
 
 #[cfg(any(unix, target_os = "hermit"))]
-pub(crate) fn set_reuseaddr(_: &net::TcpListener, _: bool) -> io::Result<()> {
+pub(crate) fn set_reuseaddr<S>(_: &S, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn reuseaddr<S>(_: &S) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+pub(crate) fn set_reuseport<S>(_: &S, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+pub(crate) fn reuseport<S>(_: &S) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_only_v6<S>(_: &S, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn only_v6<S>(_: &S) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_send_buffer_size<S>(_: &S, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn send_buffer_size<S>(_: &S) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_recv_buffer_size<S>(_: &S, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_buffer_size<S>(_: &S) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn local_addr<S>(_: &S) -> io::Result<SocketAddr> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_keepalive<S>(_: &S, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn keepalive<S>(_: &S) -> io::Result<bool> {
+    os_required!();
+}
+
+cfg_tcp_keepalive! {
+    pub(crate) fn set_keepalive_time<S>(_: &S, _: Duration) -> io::Result<()> {
+        os_required!();
+    }
+
+    pub(crate) fn keepalive_time<S>(_: &S) -> io::Result<Duration> {
+        os_required!();
+    }
+
+    pub(crate) fn set_keepalive_interval<S>(_: &S, _: Duration) -> io::Result<()> {
+        os_required!();
+    }
+
+    pub(crate) fn keepalive_interval<S>(_: &S) -> io::Result<Duration> {
+        os_required!();
+    }
+
+    pub(crate) fn set_keepalive_retries<S>(_: &S, _: u32) -> io::Result<()> {
+        os_required!();
+    }
+
+    pub(crate) fn keepalive_retries<S>(_: &S) -> io::Result<u32> {
+        os_required!();
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_user_timeout<S>(_: &S, _: Option<Duration>) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn user_timeout<S>(_: &S) -> io::Result<Option<Duration>> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_linger<S>(_: &S, _: Option<Duration>) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn linger<S>(_: &S) -> io::Result<Option<Duration>> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_recv_lowat<S>(_: &S, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_lowat<S>(_: &S) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn set_oobinline<S>(_: &S, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(unix)]
+pub(crate) fn oobinline<S>(_: &S) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn at_mark<S>(_: &S) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_notsent_lowat<S>(_: &S, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn notsent_lowat<S>(_: &S) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn tcp_info<S, T>(_: &S) -> io::Result<(T, usize)> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_congestion<S>(_: &S, _: &str) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn congestion<S>(_: &S) -> io::Result<String> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_cork<S>(_: &S, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn cork<S>(_: &S) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_quickack<S>(_: &S, _: bool) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn quickack<S>(_: &S) -> io::Result<bool> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_defer_accept<S>(_: &S, _: Option<Duration>) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn defer_accept<S>(_: &S) -> io::Result<Option<Duration>> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_fastopen<S>(_: &S, _: u32) -> io::Result<()> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn fastopen<S>(_: &S) -> io::Result<u32> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn connect_with_data<S>(_: &S, _: SocketAddr, _: &[u8]) -> io::Result<usize> {
+    os_required!();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn sendfile<S>(_: &S, _: &std::fs::File, _: u64, _: usize) -> io::Result<usize> {
     os_required!();
 }
 
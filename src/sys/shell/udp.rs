@@ -6,6 +6,98 @@ pub fn bind(_: SocketAddr) -> io::Result<net::UdpSocket> {
     os_required!()
 }
 
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+pub(crate) fn bind_reuseport(_: SocketAddr) -> io::Result<net::UdpSocket> {
+    os_required!()
+}
+
 pub(crate) fn only_v6(_: &net::UdpSocket) -> io::Result<bool> {
     os_required!()
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_gro(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn gro(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_segmented(
+    _: &net::UdpSocket,
+    _: &[u8],
+    _: Option<SocketAddr>,
+    _: u16,
+) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_from_segmented(
+    _: &net::UdpSocket,
+    _: &mut [u8],
+) -> io::Result<(usize, SocketAddr, Option<usize>)> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_pktinfo(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_pktinfo(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_ttl(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_ttl(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_timestamp(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_timestamp(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_overflow(_: &net::UdpSocket, _: bool) -> io::Result<()> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_overflow(_: &net::UdpSocket) -> io::Result<bool> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_msg(
+    _: &net::UdpSocket,
+    _: &[u8],
+    _: Option<SocketAddr>,
+    _: &[u8],
+) -> io::Result<usize> {
+    os_required!()
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_msg(
+    _: &net::UdpSocket,
+    _: &mut [u8],
+    _: &mut [u8],
+) -> io::Result<(usize, SocketAddr, usize, bool)> {
+    os_required!()
+}
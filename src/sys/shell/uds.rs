@@ -13,6 +13,45 @@ pub(crate) mod datagram {
     pub(crate) fn pair() -> io::Result<(net::UnixDatagram, net::UnixDatagram)> {
         os_required!()
     }
+
+    pub(crate) fn send_to_with_flags(
+        _: &net::UnixDatagram,
+        _: &[u8],
+        _: &SocketAddr,
+        _: crate::net::MsgFlags,
+    ) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_from_with_flags(
+        _: &net::UnixDatagram,
+        _: &mut [u8],
+        _: crate::net::MsgFlags,
+    ) -> io::Result<(usize, SocketAddr)> {
+        os_required!()
+    }
+
+    pub(crate) fn send_many(
+        _: &net::UnixDatagram,
+        _: &[crate::net::SendDatagram<'_, SocketAddr>],
+    ) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_many(
+        _: &net::UnixDatagram,
+        _: &mut [crate::net::RecvDatagram<'_, SocketAddr>],
+    ) -> io::Result<usize> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_from_uninit(
+        _: &net::UnixDatagram,
+        _: &mut [std::mem::MaybeUninit<u8>],
+        _: crate::net::MsgFlags,
+    ) -> io::Result<(usize, SocketAddr)> {
+        os_required!()
+    }
 }
 
 pub(crate) mod listener {
@@ -41,4 +80,12 @@ pub(crate) mod stream {
     pub(crate) fn pair() -> io::Result<(net::UnixStream, net::UnixStream)> {
         os_required!()
     }
+
+    pub(crate) fn set_recv_lowat(_: &net::UnixStream, _: u32) -> io::Result<()> {
+        os_required!()
+    }
+
+    pub(crate) fn recv_lowat(_: &net::UnixStream) -> io::Result<u32> {
+        os_required!()
+    }
 }
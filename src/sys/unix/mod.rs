@@ -126,10 +126,21 @@ cfg_os_poll! {
     #[cfg(feature = "os-ext")]
     mod sourcefd;
     #[cfg(feature = "os-ext")]
-    pub use self::sourcefd::SourceFd;
+    pub use self::sourcefd::{BorrowedSource, OwnedSource, SourceFd};
 
     cfg_net! {
         mod net;
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        pub(crate) use self::net::{
+            recv_zerocopy_completion, send_zerocopy, set_zerocopy, steer_reuseport_by_cpu, zerocopy,
+        };
+        #[cfg(not(any(target_os = "hermit", target_os = "wasi")))]
+        mod msg;
+        #[cfg(not(any(target_os = "hermit", target_os = "wasi")))]
+        pub(crate) use self::msg::{
+            recv_from_uninit, recv_from_with_flags, recv_many, recv_uninit, recv_with_flags, send_many,
+            send_to_with_flags, send_with_flags,
+        };
 
         pub(crate) mod tcp;
         pub(crate) mod udp;
@@ -177,6 +188,6 @@ cfg_os_poll! {
 cfg_not_os_poll! {
     cfg_os_ext! {
         mod sourcefd;
-        pub use self::sourcefd::SourceFd;
+        pub use self::sourcefd::{BorrowedSource, OwnedSource, SourceFd};
     }
 }
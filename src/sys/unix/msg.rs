@@ -0,0 +1,324 @@
+use std::io;
+use std::mem::{self, size_of, MaybeUninit};
+use std::net::SocketAddr;
+use std::ptr;
+
+use crate::net::{MsgFlags, RecvDatagram, SendDatagram};
+use crate::sys::unix::net::{socket_addr, to_socket_addr};
+
+pub(crate) fn send_with_flags(fd: libc::c_int, buf: &[u8], flags: MsgFlags) -> io::Result<usize> {
+    syscall!(send(fd, buf.as_ptr().cast(), buf.len(), flags.as_raw())).map(|n| n as usize)
+}
+
+pub(crate) fn send_to_with_flags(
+    fd: libc::c_int,
+    buf: &[u8],
+    addr: SocketAddr,
+    flags: MsgFlags,
+) -> io::Result<usize> {
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+    syscall!(sendto(
+        fd,
+        buf.as_ptr().cast(),
+        buf.len(),
+        flags.as_raw(),
+        raw_addr.as_ptr(),
+        raw_addr_length,
+    ))
+    .map(|n| n as usize)
+}
+
+/// Note that with `MSG_TRUNC` the returned length may be larger than `buf`.
+pub(crate) fn recv_with_flags(
+    fd: libc::c_int,
+    buf: &mut [u8],
+    flags: MsgFlags,
+) -> io::Result<usize> {
+    syscall!(recv(fd, buf.as_mut_ptr().cast(), buf.len(), flags.as_raw())).map(|n| n as usize)
+}
+
+/// Same as [`recv_with_flags`], but `buf` doesn't have to be initialised.
+pub(crate) fn recv_uninit(
+    fd: libc::c_int,
+    buf: &mut [MaybeUninit<u8>],
+    flags: MsgFlags,
+) -> io::Result<usize> {
+    syscall!(recv(fd, buf.as_mut_ptr().cast(), buf.len(), flags.as_raw())).map(|n| n as usize)
+}
+
+/// Note that with `MSG_TRUNC` the returned length may be larger than `buf`.
+pub(crate) fn recv_from_with_flags(
+    fd: libc::c_int,
+    buf: &mut [u8],
+    flags: MsgFlags,
+) -> io::Result<(usize, SocketAddr)> {
+    recv_from(fd, buf.as_mut_ptr().cast(), buf.len(), flags)
+}
+
+/// Same as [`recv_from_with_flags`], but `buf` doesn't have to be initialised.
+pub(crate) fn recv_from_uninit(
+    fd: libc::c_int,
+    buf: &mut [MaybeUninit<u8>],
+    flags: MsgFlags,
+) -> io::Result<(usize, SocketAddr)> {
+    recv_from(fd, buf.as_mut_ptr().cast(), buf.len(), flags)
+}
+
+fn recv_from(
+    fd: libc::c_int,
+    buf: *mut libc::c_void,
+    len: usize,
+    flags: MsgFlags,
+) -> io::Result<(usize, SocketAddr)> {
+    let mut storage = MaybeUninit::<libc::sockaddr_storage>::zeroed();
+    let mut length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    let n = syscall!(recvfrom(
+        fd,
+        buf,
+        len,
+        flags.as_raw(),
+        storage.as_mut_ptr().cast(),
+        &mut length,
+    ))?;
+    // SAFETY: `recvfrom` initialised the address.
+    let addr = unsafe { to_socket_addr(storage.as_ptr()) }?;
+    Ok((n as usize, addr))
+}
+
+/// Maximum number of datagrams sent or received in a single `send_many` or
+/// `recv_many` call.
+const MAX_BATCH: usize = 32;
+
+pub(crate) fn send_many(
+    fd: libc::c_int,
+    msgs: &[SendDatagram<'_, SocketAddr>],
+) -> io::Result<usize> {
+    send_many_with(fd, msgs, |addr, storage| {
+        let (raw_addr, raw_addr_length) = socket_addr(addr);
+        // SAFETY: `sockaddr_storage` is large enough and suitably aligned to
+        // hold any socket address.
+        unsafe { ptr::write((storage as *mut libc::sockaddr_storage).cast(), raw_addr) };
+        raw_addr_length
+    })
+}
+
+pub(crate) fn recv_many(
+    fd: libc::c_int,
+    msgs: &mut [RecvDatagram<'_, SocketAddr>],
+) -> io::Result<usize> {
+    // SAFETY: the OS initialised the address.
+    recv_many_with(fd, msgs, |storage, _| unsafe { to_socket_addr(storage) })
+}
+
+/// Send `msgs` using `sendmmsg(2)`, using `raw_addr` to write the address of
+/// a datagram into the storage and return its length.
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+))]
+pub(crate) fn send_many_with<A, F>(
+    fd: libc::c_int,
+    msgs: &[SendDatagram<'_, A>],
+    raw_addr: F,
+) -> io::Result<usize>
+where
+    F: Fn(&A, &mut libc::sockaddr_storage) -> libc::socklen_t,
+{
+    let msgs = &msgs[..msgs.len().min(MAX_BATCH)];
+    // SAFETY: all zeroes is valid for these types, they only contain integers
+    // and pointers.
+    let mut addrs: [libc::sockaddr_storage; MAX_BATCH] = unsafe { mem::zeroed() };
+    let mut iovs: [libc::iovec; MAX_BATCH] = unsafe { mem::zeroed() };
+    let mut hdrs: [libc::mmsghdr; MAX_BATCH] = unsafe { mem::zeroed() };
+    for (i, msg) in msgs.iter().enumerate() {
+        let addr_len = msg.addr().map_or(0, |addr| raw_addr(addr, &mut addrs[i]));
+        iovs[i] = iovec(msg.buf().as_ptr().cast_mut(), msg.buf().len());
+        hdrs[i].msg_hdr = msghdr(&mut addrs[i], addr_len, &mut iovs[i]);
+    }
+    match syscall!(sendmmsg(fd, hdrs.as_mut_ptr(), msgs.len() as _, 0)) {
+        Ok(n) => Ok(n as usize),
+        Err(ref err) if err.raw_os_error() == Some(libc::ENOSYS) => {
+            send_many_loop(fd, msgs, raw_addr)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+)))]
+pub(crate) fn send_many_with<A, F>(
+    fd: libc::c_int,
+    msgs: &[SendDatagram<'_, A>],
+    raw_addr: F,
+) -> io::Result<usize>
+where
+    F: Fn(&A, &mut libc::sockaddr_storage) -> libc::socklen_t,
+{
+    send_many_loop(fd, msgs, raw_addr)
+}
+
+/// Fallback for `send_many_with` if `sendmmsg(2)` is not available.
+fn send_many_loop<A, F>(
+    fd: libc::c_int,
+    msgs: &[SendDatagram<'_, A>],
+    raw_addr: F,
+) -> io::Result<usize>
+where
+    F: Fn(&A, &mut libc::sockaddr_storage) -> libc::socklen_t,
+{
+    for (i, msg) in msgs.iter().enumerate() {
+        // SAFETY: all zeroes is a valid `sockaddr_storage`.
+        let mut addr: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let addr_len = msg.addr().map_or(0, |a| raw_addr(a, &mut addr));
+        let mut iov = iovec(msg.buf().as_ptr().cast_mut(), msg.buf().len());
+        let hdr = msghdr(&mut addr, addr_len, &mut iov);
+        if let Err(err) = syscall!(sendmsg(fd, &hdr, 0)) {
+            // Like `sendmmsg(2)` only return the error if nothing was sent.
+            return if i == 0 { Err(err) } else { Ok(i) };
+        }
+    }
+    Ok(msgs.len())
+}
+
+/// Receive into `msgs` using `recvmmsg(2)`, using `parse_addr` to convert
+/// the address a datagram was received from.
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+))]
+pub(crate) fn recv_many_with<A, F>(
+    fd: libc::c_int,
+    msgs: &mut [RecvDatagram<'_, A>],
+    parse_addr: F,
+) -> io::Result<usize>
+where
+    F: Fn(&libc::sockaddr_storage, libc::socklen_t) -> io::Result<A>,
+{
+    reset(msgs);
+    let len = msgs.len().min(MAX_BATCH);
+    let msgs = &mut msgs[..len];
+    // SAFETY: all zeroes is valid for these types, they only contain integers
+    // and pointers.
+    let mut addrs: [libc::sockaddr_storage; MAX_BATCH] = unsafe { mem::zeroed() };
+    let mut iovs: [libc::iovec; MAX_BATCH] = unsafe { mem::zeroed() };
+    let mut hdrs: [libc::mmsghdr; MAX_BATCH] = unsafe { mem::zeroed() };
+    let addr_len = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    for (i, msg) in msgs.iter_mut().enumerate() {
+        let buf = msg.buf_mut();
+        iovs[i] = iovec(buf.as_mut_ptr(), buf.len());
+        hdrs[i].msg_hdr = msghdr(&mut addrs[i], addr_len, &mut iovs[i]);
+    }
+    let n = match syscall!(recvmmsg(
+        fd,
+        hdrs.as_mut_ptr(),
+        len as _,
+        0,
+        ptr::null_mut()
+    )) {
+        Ok(n) => n as usize,
+        Err(ref err) if err.raw_os_error() == Some(libc::ENOSYS) => {
+            return recv_many_loop(fd, msgs, parse_addr)
+        }
+        Err(err) => return Err(err),
+    };
+    for (i, msg) in msgs.iter_mut().enumerate().take(n) {
+        let hdr = &hdrs[i];
+        let addr = match parse_addr(&addrs[i], hdr.msg_hdr.msg_namelen) {
+            Ok(addr) => addr,
+            // Don't lose the datagrams that are already filled in.
+            Err(err) => return if i == 0 { Err(err) } else { Ok(i) },
+        };
+        let truncated = hdr.msg_hdr.msg_flags & libc::MSG_TRUNC != 0;
+        msg.set_received(hdr.msg_len as usize, addr, truncated);
+    }
+    Ok(n)
+}
+
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+)))]
+pub(crate) fn recv_many_with<A, F>(
+    fd: libc::c_int,
+    msgs: &mut [RecvDatagram<'_, A>],
+    parse_addr: F,
+) -> io::Result<usize>
+where
+    F: Fn(&libc::sockaddr_storage, libc::socklen_t) -> io::Result<A>,
+{
+    reset(msgs);
+    recv_many_loop(fd, msgs, parse_addr)
+}
+
+/// Fallback for `recv_many_with` if `recvmmsg(2)` is not available.
+fn recv_many_loop<A, F>(
+    fd: libc::c_int,
+    msgs: &mut [RecvDatagram<'_, A>],
+    parse_addr: F,
+) -> io::Result<usize>
+where
+    F: Fn(&libc::sockaddr_storage, libc::socklen_t) -> io::Result<A>,
+{
+    for (i, msg) in msgs.iter_mut().enumerate() {
+        // SAFETY: all zeroes is a valid `sockaddr_storage`.
+        let mut addr: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let addr_len = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        let buf = msg.buf_mut();
+        let mut iov = iovec(buf.as_mut_ptr(), buf.len());
+        let mut hdr = msghdr(&mut addr, addr_len, &mut iov);
+        let result = syscall!(recvmsg(fd, &mut hdr, 0))
+            .and_then(|n| Ok((n, parse_addr(&addr, hdr.msg_namelen)?)));
+        match result {
+            Ok((n, addr)) => {
+                let truncated = hdr.msg_flags & libc::MSG_TRUNC != 0;
+                msg.set_received(n as usize, addr, truncated);
+            }
+            // Like `recvmmsg(2)` only return the error if nothing was received.
+            Err(err) => return if i == 0 { Err(err) } else { Ok(i) },
+        }
+    }
+    Ok(msgs.len())
+}
+
+/// Reset all `msgs`, so that only the received datagrams contain data.
+fn reset<A>(msgs: &mut [RecvDatagram<'_, A>]) {
+    for msg in msgs {
+        msg.reset();
+    }
+}
+
+fn iovec(base: *mut u8, len: usize) -> libc::iovec {
+    libc::iovec {
+        iov_base: base.cast(),
+        iov_len: len,
+    }
+}
+
+/// Returns a `msghdr` for a single `iov`, `name` is ignored if `name_len` is
+/// zero.
+fn msghdr(
+    name: *mut libc::sockaddr_storage,
+    name_len: libc::socklen_t,
+    iov: *mut libc::iovec,
+) -> libc::msghdr {
+    // SAFETY: all zeroes is a valid `msghdr`, it only contains integers and
+    // pointers.
+    let mut hdr: libc::msghdr = unsafe { mem::zeroed() };
+    if name_len != 0 {
+        hdr.msg_name = name.cast();
+        hdr.msg_namelen = name_len;
+    }
+    hdr.msg_iov = iov;
+    hdr.msg_iovlen = 1;
+    hdr
+}
@@ -1,7 +1,14 @@
 use std::io;
 use std::mem::size_of;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::mem::size_of_val;
+#[cfg(unix)]
+use std::mem::MaybeUninit;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 
+#[cfg(target_os = "linux")]
+use libc::{MSG_ZEROCOPY, SO_ZEROCOPY};
+
 pub(crate) fn new_ip_socket(addr: SocketAddr, socket_type: libc::c_int) -> io::Result<libc::c_int> {
     let domain = match addr {
         SocketAddr::V4(..) => libc::AF_INET,
@@ -82,6 +89,189 @@ pub(crate) fn new_socket(domain: libc::c_int, socket_type: libc::c_int) -> io::R
     Ok(socket)
 }
 
+/// Set the socket option `name` at `level` to `value`.
+#[cfg(unix)]
+pub(crate) fn set_socket_option<T>(
+    fd: libc::c_int,
+    level: libc::c_int,
+    name: libc::c_int,
+    value: T,
+) -> io::Result<()> {
+    syscall!(setsockopt(
+        fd,
+        level,
+        name,
+        &value as *const T as *const libc::c_void,
+        size_of::<T>() as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
+
+/// Get the value of the socket option `name` at `level`.
+///
+/// `T` must be a type for which all bit patterns are valid, e.g. an integer or
+/// a C struct of integers.
+#[cfg(unix)]
+pub(crate) fn socket_option<T>(
+    fd: libc::c_int,
+    level: libc::c_int,
+    name: libc::c_int,
+) -> io::Result<T> {
+    socket_option_len(fd, level, name).map(|(value, _)| value)
+}
+
+/// Same as [`socket_option`], but also returns the number of bytes written by
+/// the OS, which can be less than the size of `T`. The remaining bytes are
+/// zeroed.
+#[cfg(unix)]
+pub(crate) fn socket_option_len<T>(
+    fd: libc::c_int,
+    level: libc::c_int,
+    name: libc::c_int,
+) -> io::Result<(T, usize)> {
+    let mut value: MaybeUninit<T> = MaybeUninit::zeroed();
+    let mut length = size_of::<T>() as libc::socklen_t;
+    syscall!(getsockopt(
+        fd,
+        level,
+        name,
+        value.as_mut_ptr().cast(),
+        &mut length,
+    ))?;
+    // SAFETY: the value was zeroed and (partially) initialised by `getsockopt`,
+    // the caller ensures all bit patterns are valid for `T`.
+    Ok((unsafe { value.assume_init() }, length as usize))
+}
+
+/// Attach a classic BPF program to the `SO_REUSEPORT` group of `fd` that
+/// selects the socket at index `cpu % size` in the group, where `cpu` is the
+/// CPU that received the packet or connection.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn steer_reuseport_by_cpu(fd: libc::c_int, size: u32) -> io::Result<()> {
+    const fn stmt(code: u32, k: u32) -> libc::sock_filter {
+        libc::sock_filter {
+            code: code as u16,
+            jt: 0,
+            jf: 0,
+            k,
+        }
+    }
+
+    let mut filter = [
+        // A = the CPU that received the packet.
+        stmt(
+            libc::BPF_LD | libc::BPF_W | libc::BPF_ABS,
+            (libc::SKF_AD_OFF + libc::SKF_AD_CPU) as u32,
+        ),
+        // A = A % size.
+        stmt(libc::BPF_ALU | libc::BPF_MOD | libc::BPF_K, size),
+        // Return A, the index of the socket in the group.
+        stmt(libc::BPF_RET | libc::BPF_A, 0),
+    ];
+    let program = libc::sock_fprog {
+        len: filter.len() as libc::c_ushort,
+        filter: filter.as_mut_ptr(),
+    };
+    set_socket_option(
+        fd,
+        libc::SOL_SOCKET,
+        libc::SO_ATTACH_REUSEPORT_CBPF,
+        program,
+    )
+}
+
+// Not defined by libc for Android. All architectures supported by Android use
+// the generic values.
+#[cfg(target_os = "android")]
+const SO_ZEROCOPY: libc::c_int = 60;
+#[cfg(target_os = "android")]
+const MSG_ZEROCOPY: libc::c_int = 0x4000000;
+// Not defined by libc, but the same on all architectures.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const SO_EE_ORIGIN_ZEROCOPY: u8 = 5;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const SO_EE_CODE_ZEROCOPY_COPIED: u8 = 1;
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_zerocopy(fd: libc::c_int, zerocopy: bool) -> io::Result<()> {
+    let zerocopy = zerocopy as libc::c_int;
+    set_socket_option(fd, libc::SOL_SOCKET, SO_ZEROCOPY, zerocopy)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn zerocopy(fd: libc::c_int) -> io::Result<bool> {
+    socket_option::<libc::c_int>(fd, libc::SOL_SOCKET, SO_ZEROCOPY).map(|zerocopy| zerocopy != 0)
+}
+
+/// Send `buf` using `MSG_ZEROCOPY`, to `addr` if provided.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_zerocopy(
+    fd: libc::c_int,
+    buf: &[u8],
+    addr: Option<SocketAddr>,
+) -> io::Result<usize> {
+    let flags = MSG_ZEROCOPY | libc::MSG_NOSIGNAL;
+    let n = match addr {
+        Some(addr) => {
+            let (raw_addr, raw_addr_length) = socket_addr(&addr);
+            syscall!(sendto(
+                fd,
+                buf.as_ptr().cast(),
+                buf.len(),
+                flags,
+                raw_addr.as_ptr(),
+                raw_addr_length,
+            ))?
+        }
+        None => syscall!(send(fd, buf.as_ptr().cast(), buf.len(), flags))?,
+    };
+    Ok(n as usize)
+}
+
+/// Receive a `MSG_ZEROCOPY` completion notification from the error queue.
+///
+/// Returns the first and last sequence number, and whether or not the data was
+/// copied.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_zerocopy_completion(fd: libc::c_int) -> io::Result<(u32, u32, bool)> {
+    // Aligned for `cmsghdr`.
+    let mut control = [0u64; 16];
+    // SAFETY: all zeroes is a valid `msghdr`.
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = size_of_val(&control) as _;
+    syscall!(recvmsg(fd, &mut msg, libc::MSG_ERRQUEUE))?;
+
+    // SAFETY: `msg` is initialised by `recvmsg` above.
+    let mut cmsg = unsafe { libc::CMSG_FIRSTHDR(&msg) };
+    while !cmsg.is_null() {
+        // SAFETY: `cmsg` is not null and points into `control`.
+        let (level, kind) = unsafe { ((*cmsg).cmsg_level, (*cmsg).cmsg_type) };
+        if (level == libc::SOL_IP && kind == libc::IP_RECVERR)
+            || (level == libc::SOL_IPV6 && kind == libc::IPV6_RECVERR)
+        {
+            // SAFETY: the data of these messages is a `sock_extended_err`.
+            let err: libc::sock_extended_err = unsafe {
+                libc::CMSG_DATA(cmsg)
+                    .cast::<libc::sock_extended_err>()
+                    .read_unaligned()
+            };
+            if err.ee_origin != SO_EE_ORIGIN_ZEROCOPY {
+                // E.g. an ICMP error.
+                return Err(io::Error::from_raw_os_error(err.ee_errno as libc::c_int));
+            }
+            let copied = err.ee_code & SO_EE_CODE_ZEROCOPY_COPIED != 0;
+            return Ok((err.ee_info, err.ee_data, copied));
+        }
+        // SAFETY: same as `CMSG_FIRSTHDR` above.
+        cmsg = unsafe { libc::CMSG_NXTHDR(&msg, cmsg) };
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "error queue message without extended error",
+    ))
+}
+
 /// A type with the same memory layout as `libc::sockaddr`. Used in converting Rust level
 /// SocketAddr* types into their system representation. The benefit of this specific
 /// type over using `libc::sockaddr_storage` is that this type is exactly as large as it
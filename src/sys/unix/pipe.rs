@@ -67,10 +67,12 @@ pub(crate) fn new_raw() -> io::Result<[RawFd; 2]> {
 cfg_os_ext! {
 use std::fs::File;
 use std::io::{IoSlice, IoSliceMut, Read, Write};
+use std::mem::MaybeUninit;
 use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd};
 use std::process::{ChildStderr, ChildStdin, ChildStdout};
 
 use crate::io_source::IoSource;
+use crate::unix::OwnedSource;
 use crate::{event, Interest, Registry, Token};
 
 /// Create a new non-blocking Unix pipe.
@@ -296,6 +298,27 @@ impl Sender {
     {
         self.inner.do_io(|_| f())
     }
+
+    /// Move up to `len` bytes from `source` into the pipe using
+    /// [`splice(2)`], without copying the data through userspace.
+    ///
+    /// `source` is usually a socket, such as a [`TcpStream`]. Returns the
+    /// number of bytes moved, which can be less than `len`, or zero if
+    /// `source` reached end of file. Returns a [`WouldBlock`] error if the pipe
+    /// is full or if no data is available in `source`.
+    ///
+    /// This only ensures the pipe receives more events after a [`WouldBlock`]
+    /// error, if `source` is a Mio type this call should be wrapped in its
+    /// `try_io` method.
+    ///
+    /// [`splice(2)`]: https://man7.org/linux/man-pages/man2/splice.2.html
+    /// [`TcpStream`]: crate::net::TcpStream
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn splice_from<S: AsFd>(&self, source: &S, len: usize) -> io::Result<usize> {
+        self.inner
+            .do_io(|sender| splice(source.as_fd().as_raw_fd(), sender.as_raw_fd(), len))
+    }
 }
 
 impl event::Source for Sender {
@@ -400,6 +423,18 @@ impl From<OwnedFd> for Sender {
     }
 }
 
+impl<T: AsFd + Into<OwnedFd>> From<OwnedSource<T>> for Sender {
+    /// Converts an `OwnedSource` to a `Sender`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the FD is in non-blocking
+    /// mode and that it's deregistered from any `Poll` instance.
+    fn from(source: OwnedSource<T>) -> Self {
+        Sender::from(OwnedFd::from(source))
+    }
+}
+
 /// Receiving end of an Unix pipe.
 ///
 /// See [`new`] for documentation, including examples.
@@ -481,6 +516,70 @@ impl Receiver {
     {
         self.inner.do_io(|_| f())
     }
+
+    /// Read from the pipe into `buf`, which doesn't have to be initialised.
+    ///
+    /// On success returns the number of bytes read, `n`, after which the first
+    /// `n` bytes of `buf` are initialised. Like [`read`] this returns zero if
+    /// all senders are closed and the pipe is empty, and a [`WouldBlock`]
+    /// error if the pipe is empty.
+    ///
+    /// This uses `read(2)`, but is named after the socket methods, such as
+    /// [`TcpStream::recv_uninit`], so that all types reading into
+    /// uninitialised buffers share the same name.
+    ///
+    /// [`read`]: Read::read
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`TcpStream::recv_uninit`]: crate::net::TcpStream::recv_uninit
+    pub fn recv_uninit(&self, buf: &mut [MaybeUninit<u8>]) -> io::Result<usize> {
+        self.inner.do_io(|receiver| {
+            syscall!(read(receiver.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len()))
+                .map(|n| n as usize)
+        })
+    }
+
+    /// Move up to `len` bytes from the pipe into `destination` using
+    /// [`splice(2)`], without copying the data through userspace.
+    ///
+    /// `destination` is usually a socket, such as a [`TcpStream`]. Returns the
+    /// number of bytes moved, which can be less than `len`, or zero if all
+    /// senders are closed and the pipe is empty. Returns a [`WouldBlock`]
+    /// error if the pipe is empty or if `destination` is not ready for
+    /// writing.
+    ///
+    /// This only ensures the pipe receives more events after a [`WouldBlock`]
+    /// error, if `destination` is a Mio type this call should be wrapped in
+    /// its `try_io` method.
+    ///
+    /// [`splice(2)`]: https://man7.org/linux/man-pages/man2/splice.2.html
+    /// [`TcpStream`]: crate::net::TcpStream
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn splice_to<S: AsFd>(&self, destination: &S, len: usize) -> io::Result<usize> {
+        self.inner
+            .do_io(|receiver| splice(receiver.as_raw_fd(), destination.as_fd().as_raw_fd(), len))
+    }
+
+    /// Copy up to `len` bytes from the pipe into the pipe of `destination`
+    /// using [`tee(2)`], without consuming the data from this pipe.
+    ///
+    /// Returns the number of bytes copied, which can be less than `len`, or
+    /// zero if all senders are closed and the pipe is empty. Returns a
+    /// [`WouldBlock`] error if this pipe is empty or if the pipe of
+    /// `destination` is full.
+    ///
+    /// [`tee(2)`]: https://man7.org/linux/man-pages/man2/tee.2.html
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn tee(&self, destination: &Sender, len: usize) -> io::Result<usize> {
+        destination.inner.do_io(|sender| {
+            self.inner.do_io(|receiver| {
+                let flags = libc::SPLICE_F_NONBLOCK;
+                syscall!(tee(receiver.as_raw_fd(), sender.as_raw_fd(), len, flags))
+                    .map(|n| n as usize)
+            })
+        })
+    }
 }
 
 impl event::Source for Receiver {
@@ -587,6 +686,25 @@ impl From<OwnedFd> for Receiver {
     }
 }
 
+impl<T: AsFd + Into<OwnedFd>> From<OwnedSource<T>> for Receiver {
+    /// Converts an `OwnedSource` to a `Receiver`.
+    ///
+    /// # Notes
+    ///
+    /// The caller is responsible for ensuring that the FD is in non-blocking
+    /// mode and that it's deregistered from any `Poll` instance.
+    fn from(source: OwnedSource<T>) -> Self {
+        Receiver::from(OwnedFd::from(source))
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn splice(from: RawFd, to: RawFd, len: usize) -> io::Result<usize> {
+    let flags = libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK;
+    let null = std::ptr::null_mut();
+    syscall!(splice(from, null, to, null, len, flags)).map(|n| n as usize)
+}
+
 #[cfg(not(any(target_os = "aix", target_os = "illumos", target_os = "solaris", target_os = "vita")))]
 fn set_nonblocking(fd: RawFd, nonblocking: bool) -> io::Result<()> {
     let value = nonblocking as libc::c_int;
@@ -202,6 +202,41 @@ pub mod event {
         false
     }
 
+    cfg_test_util! {
+        /// Add an event with `readiness` for `token` to `events`.
+        pub fn from_readiness(
+            token: Token,
+            readiness: crate::test_util::Readiness,
+            events: &mut crate::sys::Events,
+        ) {
+            let mut kind = 0;
+            if readiness.is_readable() {
+                kind |= libc::EPOLLIN;
+            }
+            if readiness.is_writable() {
+                kind |= libc::EPOLLOUT;
+            }
+            if readiness.is_error() {
+                kind |= libc::EPOLLERR;
+            }
+            if readiness.is_priority() {
+                kind |= libc::EPOLLPRI;
+            }
+            match (readiness.is_read_closed(), readiness.is_write_closed()) {
+                (true, true) => kind |= libc::EPOLLHUP,
+                (true, false) => kind |= libc::EPOLLIN | libc::EPOLLRDHUP,
+                (false, true) => kind |= libc::EPOLLOUT | libc::EPOLLERR,
+                (false, false) => {}
+            }
+            events.push(libc::epoll_event {
+                events: kind as u32,
+                u64: usize::from(token) as u64,
+                #[cfg(target_os = "redox")]
+                _pad: 0,
+            });
+        }
+    }
+
     pub fn debug_details(f: &mut fmt::Formatter<'_>, event: &Event) -> fmt::Result {
         #[allow(clippy::trivially_copy_pass_by_ref)]
         fn check_events(got: &u32, want: &libc::c_int) -> bool {
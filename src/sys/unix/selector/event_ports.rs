@@ -693,6 +693,39 @@ pub mod event {
         false
     }
 
+    cfg_test_util! {
+        /// Add an event with `readiness` for `token` to `events`.
+        pub fn from_readiness(
+            token: Token,
+            readiness: crate::test_util::Readiness,
+            events: &mut crate::sys::Events,
+        ) {
+            let mut kind = 0;
+            if readiness.is_readable() {
+                kind |= libc::POLLIN as EventMask;
+            }
+            if readiness.is_writable() {
+                kind |= libc::POLLOUT as EventMask;
+            }
+            if readiness.is_error() {
+                kind |= libc::POLLERR as EventMask;
+            }
+            if readiness.is_priority() {
+                kind |= libc::POLLPRI as EventMask;
+            }
+            match (readiness.is_read_closed(), readiness.is_write_closed()) {
+                (true, _) => kind |= libc::POLLHUP as EventMask,
+                (false, true) => kind |= (libc::POLLOUT | libc::POLLERR) as EventMask,
+                (false, false) => {}
+            }
+            // SAFETY: all zero is a valid `port_event`.
+            let mut event: libc::port_event = unsafe { std::mem::zeroed() };
+            event.portev_events = kind;
+            event.portev_user = usize::from(token) as _;
+            events.push(Event(event));
+        }
+    }
+
     pub fn debug_details(f: &mut fmt::Formatter<'_>, event: &Event) -> fmt::Result {
         #[allow(clippy::trivially_copy_pass_by_ref)]
         fn check_events(got: &EventMask, want: &libc::c_short) -> bool {
@@ -780,17 +813,20 @@ cfg_io_source! {
             } else {
                 let selector = registry.selector().try_clone()?;
 
-                selector.register_internal(fd, token, interests).map(move |shared_record| {
-                    let state = InternalState {
-                        selector,
-                        token,
-                        interests,
-                        fd,
-                        shared_record,
-                    };
-
-                    self.inner = Some(Box::new(state));
-                })
+                registry
+                    .fault_selector()
+                    .register_internal(fd, token, interests)
+                    .map(move |shared_record| {
+                        let state = InternalState {
+                            selector,
+                            token,
+                            interests,
+                            fd,
+                            shared_record,
+                        };
+
+                        self.inner = Some(Box::new(state));
+                    })
             }
         }
 
@@ -802,7 +838,7 @@ cfg_io_source! {
             fd: RawFd,
         ) -> io::Result<()> {
             match self.inner.as_mut() {
-                Some(state) => registry.selector().reregister(fd, token, interests).map(|()| {
+                Some(state) => registry.fault_selector().reregister(fd, token, interests).map(|()| {
                     state.token = token;
                     state.interests = interests;
                 }),
@@ -815,7 +851,7 @@ cfg_io_source! {
                 state.shared_record.mark_unregistered();
             }
 
-            registry.selector().deregister(fd)
+            registry.fault_selector().deregister(fd)
         }
     }
 }
@@ -490,6 +490,59 @@ pub mod event {
         }
     }
 
+    cfg_test_util! {
+        /// Add an event with `readiness` for `token` to `events`.
+        ///
+        /// This adds an event for each filter, priority readiness is ignored
+        /// as kqueue doesn't support it.
+        pub fn from_readiness(
+            token: Token,
+            readiness: crate::test_util::Readiness,
+            events: &mut crate::sys::Events,
+        ) {
+            let new = |filter: Filter, flags: Flags| {
+                // SAFETY: all zero is a valid `kevent`.
+                let mut event: libc::kevent = unsafe { std::mem::zeroed() };
+                event.filter = filter;
+                event.flags = flags;
+                event.udata = usize::from(token) as super::UData;
+                Event(event)
+            };
+
+            let start = events.len();
+            if readiness.is_readable() || readiness.is_read_closed() {
+                let flags = if readiness.is_read_closed() { libc::EV_EOF } else { 0 };
+                events.push(new(libc::EVFILT_READ as Filter, flags));
+            }
+            if readiness.is_writable() || readiness.is_write_closed() {
+                let flags = if readiness.is_write_closed() { libc::EV_EOF } else { 0 };
+                events.push(new(libc::EVFILT_WRITE as Filter, flags));
+            }
+            #[cfg(any(
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "ios",
+                target_os = "macos",
+                target_os = "tvos",
+                target_os = "visionos",
+                target_os = "watchos",
+            ))]
+            if readiness.is_aio() {
+                events.push(new(libc::EVFILT_AIO as Filter, 0));
+            }
+            #[cfg(target_os = "freebsd")]
+            if readiness.is_lio() {
+                events.push(new(libc::EVFILT_LIO as Filter, 0));
+            }
+            if readiness.is_error() {
+                match events.get_mut(start) {
+                    Some(event) => event.0.flags |= libc::EV_ERROR,
+                    None => events.push(new(0, libc::EV_ERROR)),
+                }
+            }
+        }
+    }
+
     pub fn debug_details(f: &mut fmt::Formatter<'_>, event: &Event) -> fmt::Result {
         debug_detail!(
             FilterDetails(Filter),
@@ -648,6 +648,39 @@ pub mod event {
         false
     }
 
+    cfg_test_util! {
+        /// Add an event with `readiness` for `token` to `events`.
+        pub fn from_readiness(
+            token: Token,
+            readiness: crate::test_util::Readiness,
+            events: &mut crate::sys::Events,
+        ) {
+            let mut kind = 0;
+            if readiness.is_readable() {
+                kind |= libc::POLLIN;
+            }
+            if readiness.is_writable() {
+                kind |= libc::POLLOUT;
+            }
+            if readiness.is_error() {
+                kind |= libc::POLLERR;
+            }
+            if readiness.is_priority() {
+                kind |= POLLPRI;
+            }
+            match (readiness.is_read_closed(), readiness.is_write_closed()) {
+                (true, false) if POLLRDHUP != 0 => kind |= libc::POLLIN | POLLRDHUP,
+                (true, _) => kind |= libc::POLLHUP,
+                (false, true) => kind |= libc::POLLOUT | libc::POLLERR,
+                (false, false) => {}
+            }
+            events.push(Event {
+                token,
+                events: kind,
+            });
+        }
+    }
+
     pub fn debug_details(f: &mut fmt::Formatter<'_>, event: &Event) -> fmt::Result {
         #[allow(clippy::trivially_copy_pass_by_ref)]
         fn check_events(got: &super::PollFlagInt, want: &super::PollFlagInt) -> bool {
@@ -754,17 +787,20 @@ cfg_io_source! {
             } else {
                 let selector = registry.selector().try_clone()?;
 
-                selector.register_internal(fd, token, interests).map(move |shared_record| {
-                    let state = InternalState {
-                        selector,
-                        token,
-                        interests,
-                        fd,
-                        shared_record,
-                    };
-
-                    self.inner = Some(Box::new(state));
-                })
+                registry
+                    .fault_selector()
+                    .register_internal(fd, token, interests)
+                    .map(move |shared_record| {
+                        let state = InternalState {
+                            selector,
+                            token,
+                            interests,
+                            fd,
+                            shared_record,
+                        };
+
+                        self.inner = Some(Box::new(state));
+                    })
             }
         }
 
@@ -777,7 +813,7 @@ cfg_io_source! {
         ) -> io::Result<()> {
             match self.inner.as_mut() {
                 Some(state) => registry
-                .selector()
+                .fault_selector()
                 .reregister(fd, token, interests)
                 .map(|()| {
                     state.token = token;
@@ -794,7 +830,7 @@ cfg_io_source! {
                 state.shared_record.mark_unregistered();
             }
 
-            registry.selector().deregister(fd)
+            registry.fault_selector().deregister(fd)
         }
     }
 }
@@ -29,7 +29,7 @@ impl IoSourceState {
         fd: RawFd,
     ) -> io::Result<()> {
         // Pass through, we don't have any state.
-        registry.selector().register(fd, token, interests)
+        registry.fault_selector().register(fd, token, interests)
     }
 
     pub(crate) fn reregister(
@@ -40,11 +40,11 @@ impl IoSourceState {
         fd: RawFd,
     ) -> io::Result<()> {
         // Pass through, we don't have any state.
-        registry.selector().reregister(fd, token, interests)
+        registry.fault_selector().reregister(fd, token, interests)
     }
 
     pub(crate) fn deregister(&mut self, registry: &Registry, fd: RawFd) -> io::Result<()> {
         // Pass through, we don't have any state.
-        registry.selector().deregister(fd)
+        registry.fault_selector().deregister(fd)
     }
 }
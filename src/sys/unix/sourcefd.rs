@@ -1,10 +1,11 @@
 use std::io;
+use std::marker::PhantomData;
 #[cfg(not(target_os = "hermit"))]
-use std::os::fd::RawFd;
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd, RawFd};
 // TODO: once <https://github.com/rust-lang/rust/issues/126198> is fixed this
 // can use `std::os::fd` and be merged with the above.
 #[cfg(target_os = "hermit")]
-use std::os::hermit::io::RawFd;
+use std::os::hermit::io::{AsFd, AsRawFd, BorrowedFd, OwnedFd, RawFd};
 
 use crate::{event, Interest, Registry, Token};
 
@@ -22,6 +23,11 @@ use crate::{event, Interest, Registry, Token};
 /// that the `SourceFd` is constructed right before a call to
 /// [`Registry::register`]. See the examples for more detail.
 ///
+/// Because a `RawFd` carries no lifetime, nothing prevents the FD from being
+/// closed while it's still registered. Prefer [`BorrowedSource`], which
+/// borrows a [`BorrowedFd`], or [`OwnedSource`], which takes ownership of the
+/// FD and ties the registration to its lifetime.
+///
 /// [`event::Source`]: ../event/trait.Source.html
 /// [`Poll`]: ../struct.Poll.html
 /// [`Registry::register`]: ../struct.Registry.html#method.register
@@ -109,7 +115,109 @@ impl<'a> event::Source for SourceFd<'a> {
         token: Token,
         interests: Interest,
     ) -> io::Result<()> {
-        registry.selector().register(*self.0, token, interests)
+        registry
+            .fault_selector()
+            .register(*self.0, token, interests)?;
+        #[cfg(all(unix, feature = "test-util"))]
+        if let Some(mock) = registry.mock() {
+            mock.register(*self.0, token, interests);
+        }
+        Ok(())
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        registry
+            .fault_selector()
+            .reregister(*self.0, token, interests)?;
+        #[cfg(all(unix, feature = "test-util"))]
+        if let Some(mock) = registry.mock() {
+            mock.reregister(*self.0, token, interests);
+        }
+        Ok(())
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        registry.fault_selector().deregister(*self.0)?;
+        #[cfg(all(unix, feature = "test-util"))]
+        if let Some(mock) = registry.mock() {
+            mock.deregister(*self.0);
+        }
+        Ok(())
+    }
+}
+
+/// Adapter for a [`BorrowedFd`] providing an [`event::Source`] implementation,
+/// created by [`BorrowedSource::new`].
+///
+/// Like [`SourceFd`] this **does not** take ownership of the FD, but the
+/// borrow ensures the FD can't be closed while `BorrowedSource` is alive.
+///
+/// [`event::Source`]: ../event/trait.Source.html
+#[derive(Debug)]
+pub struct BorrowedSource<'a> {
+    fd: RawFd,
+    _borrow: PhantomData<BorrowedFd<'a>>,
+}
+
+impl<'a> BorrowedSource<'a> {
+    /// Create a new `BorrowedSource` from a [`BorrowedFd`].
+    ///
+    /// Unlike constructing a `SourceFd` from a `&RawFd`, this ensures the FD
+    /// is open for at least the lifetime of the returned source. Also available
+    /// as `BorrowedSource::from(fd)`.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+    #[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Interest, Poll, Token};
+    /// #[cfg(unix)]
+    /// use mio::unix::BorrowedSource;
+    /// #[cfg(target_os = "wasi")]
+    /// use mio::wasi::BorrowedSource;
+    ///
+    /// use std::os::fd::AsFd;
+    /// use std::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0")?;
+    ///
+    /// let poll = Poll::new()?;
+    /// poll.registry().register(
+    ///     &mut BorrowedSource::new(listener.as_fd()),
+    ///     Token(0),
+    ///     Interest::READABLE)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn new(fd: BorrowedFd<'a>) -> BorrowedSource<'a> {
+        BorrowedSource {
+            fd: fd.as_raw_fd(),
+            _borrow: PhantomData,
+        }
+    }
+}
+
+impl<'a> From<BorrowedFd<'a>> for BorrowedSource<'a> {
+    fn from(fd: BorrowedFd<'a>) -> BorrowedSource<'a> {
+        BorrowedSource::new(fd)
+    }
+}
+
+impl event::Source for BorrowedSource<'_> {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.fd).register(registry, token, interests)
     }
 
     fn reregister(
@@ -118,10 +226,136 @@ impl<'a> event::Source for SourceFd<'a> {
         token: Token,
         interests: Interest,
     ) -> io::Result<()> {
-        registry.selector().reregister(*self.0, token, interests)
+        SourceFd(&self.fd).reregister(registry, token, interests)
     }
 
     fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
-        registry.selector().deregister(*self.0)
+        SourceFd(&self.fd).deregister(registry)
+    }
+}
+
+impl AsRawFd for BorrowedSource<'_> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+/// Adapter for a type implementing [`AsFd`], providing an [`event::Source`]
+/// implementation.
+///
+/// `OwnedSource` is the owning counterpart of [`SourceFd`]. Because it owns the
+/// I/O type, the FD can't be closed while `OwnedSource` is alive. Once
+/// `OwnedSource` is dropped the FD is closed, with `epoll(2)` and `kqueue(2)`
+/// this also removes it from the OS selector, **iff** the FD was not duplicated
+/// (via [`dup(2)`]).
+///
+/// `OwnedSource` only handles registering, for I/O operations access the
+/// underlying type using [`get_ref`] or [`get_mut`].
+///
+/// [`event::Source`]: ../event/trait.Source.html
+/// [`dup(2)`]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/dup.html
+/// [`get_ref`]: OwnedSource::get_ref
+/// [`get_mut`]: OwnedSource::get_mut
+///
+/// # Examples
+///
+#[cfg_attr(all(feature = "os-poll", feature = "net"), doc = "```")]
+#[cfg_attr(not(all(feature = "os-poll", feature = "net")), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::{Interest, Poll, Token};
+/// #[cfg(unix)]
+/// use mio::unix::OwnedSource;
+/// #[cfg(target_os = "wasi")]
+/// use mio::wasi::OwnedSource;
+///
+/// use std::net::TcpListener;
+///
+/// let listener = TcpListener::bind("127.0.0.1:0")?;
+/// listener.set_nonblocking(true)?;
+/// let mut listener = OwnedSource::new(listener);
+///
+/// let poll = Poll::new()?;
+/// poll.registry().register(&mut listener, Token(0), Interest::READABLE)?;
+///
+/// // Once an event is received for the listener.
+/// # let _ =
+/// listener.get_ref().accept();
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct OwnedSource<T> {
+    inner: T,
+}
+
+impl<T: AsFd> OwnedSource<T> {
+    /// Create a new `OwnedSource`.
+    pub const fn new(inner: T) -> OwnedSource<T> {
+        OwnedSource { inner }
+    }
+
+    /// Returns a reference to the I/O type.
+    pub const fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the I/O type.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the I/O type.
+    ///
+    /// # Notes
+    ///
+    /// To ensure no more events are to be received for the FD first
+    /// [`deregister`] it.
+    ///
+    /// [`deregister`]: crate::Registry::deregister
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: AsFd> event::Source for OwnedSource<T> {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        BorrowedSource::new(self.inner.as_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        BorrowedSource::new(self.inner.as_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        BorrowedSource::new(self.inner.as_fd()).deregister(registry)
+    }
+}
+
+impl<T: AsFd> AsFd for OwnedSource<T> {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.inner.as_fd()
+    }
+}
+
+impl<T: AsFd> AsRawFd for OwnedSource<T> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_fd().as_raw_fd()
+    }
+}
+
+impl<T: AsFd + Into<OwnedFd>> From<OwnedSource<T>> for OwnedFd {
+    fn from(source: OwnedSource<T>) -> Self {
+        source.inner.into()
     }
 }
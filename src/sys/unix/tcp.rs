@@ -3,12 +3,18 @@ use std::mem::{size_of, MaybeUninit};
 use std::net::{self, SocketAddr};
 #[cfg(not(target_os = "hermit"))]
 use std::os::fd::{AsRawFd, FromRawFd};
+#[cfg(unix)]
+use std::time::Duration;
 // TODO: once <https://github.com/rust-lang/rust/issues/126198> is fixed this
 // can use `std::os::fd` and be merged with the above.
 #[cfg(target_os = "hermit")]
 use std::os::hermit::io::{AsRawFd, FromRawFd};
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys::unix::net::socket_option_len;
 use crate::sys::unix::net::{new_socket, socket_addr, to_socket_addr};
+#[cfg(unix)]
+use crate::sys::unix::net::{set_socket_option, socket_option};
 
 pub(crate) fn new_for_addr(address: SocketAddr) -> io::Result<libc::c_int> {
     let domain = match address {
@@ -18,13 +24,13 @@ pub(crate) fn new_for_addr(address: SocketAddr) -> io::Result<libc::c_int> {
     new_socket(domain, libc::SOCK_STREAM)
 }
 
-pub(crate) fn bind(socket: &net::TcpListener, addr: SocketAddr) -> io::Result<()> {
+pub(crate) fn bind<S: AsRawFd>(socket: &S, addr: SocketAddr) -> io::Result<()> {
     let (raw_addr, raw_addr_length) = socket_addr(&addr);
     syscall!(bind(socket.as_raw_fd(), raw_addr.as_ptr(), raw_addr_length))?;
     Ok(())
 }
 
-pub(crate) fn connect(socket: &net::TcpStream, addr: SocketAddr) -> io::Result<()> {
+pub(crate) fn connect<S: AsRawFd>(socket: &S, addr: SocketAddr) -> io::Result<()> {
     let (raw_addr, raw_addr_length) = socket_addr(&addr);
 
     match syscall!(connect(
@@ -37,12 +43,12 @@ pub(crate) fn connect(socket: &net::TcpStream, addr: SocketAddr) -> io::Result<(
     }
 }
 
-pub(crate) fn listen(socket: &net::TcpListener, backlog: i32) -> io::Result<()> {
+pub(crate) fn listen<S: AsRawFd>(socket: &S, backlog: i32) -> io::Result<()> {
     syscall!(listen(socket.as_raw_fd(), backlog))?;
     Ok(())
 }
 
-pub(crate) fn set_reuseaddr(socket: &net::TcpListener, reuseaddr: bool) -> io::Result<()> {
+pub(crate) fn set_reuseaddr<S: AsRawFd>(socket: &S, reuseaddr: bool) -> io::Result<()> {
     let val: libc::c_int = i32::from(reuseaddr);
     syscall!(setsockopt(
         socket.as_raw_fd(),
@@ -54,6 +60,471 @@ pub(crate) fn set_reuseaddr(socket: &net::TcpListener, reuseaddr: bool) -> io::R
     Ok(())
 }
 
+#[cfg(unix)]
+pub(crate) fn reuseaddr<S: AsRawFd>(socket: &S) -> io::Result<bool> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_REUSEADDR)?;
+    Ok(val != 0)
+}
+
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+pub(crate) fn set_reuseport<S: AsRawFd>(socket: &S, reuseport: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(reuseport);
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_REUSEPORT,
+        val,
+    )
+}
+
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+pub(crate) fn reuseport<S: AsRawFd>(socket: &S) -> io::Result<bool> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_REUSEPORT)?;
+    Ok(val != 0)
+}
+
+#[cfg(unix)]
+pub(crate) fn set_only_v6<S: AsRawFd>(socket: &S, only_v6: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(only_v6);
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::IPPROTO_IPV6,
+        libc::IPV6_V6ONLY,
+        val,
+    )
+}
+
+#[cfg(unix)]
+pub(crate) fn only_v6<S: AsRawFd>(socket: &S) -> io::Result<bool> {
+    let val: libc::c_int =
+        socket_option(socket.as_raw_fd(), libc::IPPROTO_IPV6, libc::IPV6_V6ONLY)?;
+    Ok(val != 0)
+}
+
+#[cfg(unix)]
+pub(crate) fn set_send_buffer_size<S: AsRawFd>(socket: &S, size: u32) -> io::Result<()> {
+    let val: libc::c_int = size.try_into().unwrap_or(libc::c_int::MAX);
+    set_socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_SNDBUF, val)
+}
+
+#[cfg(unix)]
+pub(crate) fn send_buffer_size<S: AsRawFd>(socket: &S) -> io::Result<u32> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_SNDBUF)?;
+    Ok(val as u32)
+}
+
+#[cfg(unix)]
+pub(crate) fn set_recv_buffer_size<S: AsRawFd>(socket: &S, size: u32) -> io::Result<()> {
+    let val: libc::c_int = size.try_into().unwrap_or(libc::c_int::MAX);
+    set_socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVBUF, val)
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_buffer_size<S: AsRawFd>(socket: &S) -> io::Result<u32> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVBUF)?;
+    Ok(val as u32)
+}
+
+#[cfg(unix)]
+pub(crate) fn local_addr<S: AsRawFd>(socket: &S) -> io::Result<SocketAddr> {
+    let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
+    let mut length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    syscall!(getsockname(
+        socket.as_raw_fd(),
+        addr.as_mut_ptr() as *mut _,
+        &mut length
+    ))?;
+    // This is safe because `getsockname` ensures the address is initialised.
+    unsafe { to_socket_addr(addr.as_ptr()) }
+}
+
+#[cfg(unix)]
+pub(crate) fn set_keepalive<S: AsRawFd>(socket: &S, keepalive: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(keepalive);
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_KEEPALIVE,
+        val,
+    )
+}
+
+#[cfg(unix)]
+pub(crate) fn keepalive<S: AsRawFd>(socket: &S) -> io::Result<bool> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_KEEPALIVE)?;
+    Ok(val != 0)
+}
+
+cfg_tcp_keepalive! {
+    /// Option used to set the idle time before keepalive probes are sent.
+    #[cfg(not(any(
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    )))]
+    const KEEPALIVE_TIME: libc::c_int = libc::TCP_KEEPIDLE;
+    #[cfg(any(
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    const KEEPALIVE_TIME: libc::c_int = libc::TCP_KEEPALIVE;
+
+    pub(crate) fn set_keepalive_time<S: AsRawFd>(
+        socket: &S,
+        time: Duration,
+    ) -> io::Result<()> {
+        let val = secs_to_c_int(time);
+        set_socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, KEEPALIVE_TIME, val)
+    }
+
+    pub(crate) fn keepalive_time<S: AsRawFd>(socket: &S) -> io::Result<Duration> {
+        let val: libc::c_int =
+            socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, KEEPALIVE_TIME)?;
+        Ok(Duration::from_secs(val as u64))
+    }
+
+    pub(crate) fn set_keepalive_interval<S: AsRawFd>(
+        socket: &S,
+        interval: Duration,
+    ) -> io::Result<()> {
+        let val = secs_to_c_int(interval);
+        set_socket_option(
+            socket.as_raw_fd(),
+            libc::IPPROTO_TCP,
+            libc::TCP_KEEPINTVL,
+            val,
+        )
+    }
+
+    pub(crate) fn keepalive_interval<S: AsRawFd>(socket: &S) -> io::Result<Duration> {
+        let val: libc::c_int =
+            socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_KEEPINTVL)?;
+        Ok(Duration::from_secs(val as u64))
+    }
+
+    pub(crate) fn set_keepalive_retries<S: AsRawFd>(socket: &S, retries: u32) -> io::Result<()> {
+        let val: libc::c_int = retries.try_into().unwrap_or(libc::c_int::MAX);
+        set_socket_option(
+            socket.as_raw_fd(),
+            libc::IPPROTO_TCP,
+            libc::TCP_KEEPCNT,
+            val,
+        )
+    }
+
+    pub(crate) fn keepalive_retries<S: AsRawFd>(socket: &S) -> io::Result<u32> {
+        let val: libc::c_int =
+            socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_KEEPCNT)?;
+        Ok(val as u32)
+    }
+
+    /// Converts `duration` to whole seconds, rounding up and capping to
+    /// `c_int::MAX`.
+    fn secs_to_c_int(duration: Duration) -> libc::c_int {
+        (duration.as_secs() + u64::from(duration.subsec_nanos() != 0))
+            .try_into()
+            .unwrap_or(libc::c_int::MAX)
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_user_timeout<S: AsRawFd>(
+    socket: &S,
+    timeout: Option<Duration>,
+) -> io::Result<()> {
+    let val: libc::c_uint = match timeout {
+        Some(timeout) => timeout.as_millis().try_into().unwrap_or(libc::c_uint::MAX),
+        None => 0,
+    };
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_USER_TIMEOUT,
+        val,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn user_timeout<S: AsRawFd>(socket: &S) -> io::Result<Option<Duration>> {
+    let val: libc::c_uint = socket_option(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_USER_TIMEOUT,
+    )?;
+    Ok((val != 0).then(|| Duration::from_millis(val.into())))
+}
+
+/// `SO_LINGER` uses ticks on Apple platforms, `SO_LINGER_SEC` uses seconds.
+#[cfg(unix)]
+#[cfg(not(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+)))]
+const LINGER: libc::c_int = libc::SO_LINGER;
+#[cfg(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos",
+))]
+const LINGER: libc::c_int = libc::SO_LINGER_SEC;
+
+#[cfg(unix)]
+pub(crate) fn set_linger<S: AsRawFd>(socket: &S, linger: Option<Duration>) -> io::Result<()> {
+    let val = libc::linger {
+        l_onoff: linger.is_some().into(),
+        l_linger: linger
+            .map(|linger| linger.as_secs().try_into().unwrap_or(libc::c_int::MAX))
+            .unwrap_or(0),
+    };
+    set_socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, LINGER, val)
+}
+
+#[cfg(unix)]
+pub(crate) fn linger<S: AsRawFd>(socket: &S) -> io::Result<Option<Duration>> {
+    let val: libc::linger = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, LINGER)?;
+    Ok((val.l_onoff != 0).then(|| Duration::from_secs(val.l_linger as u64)))
+}
+
+#[cfg(unix)]
+pub(crate) fn set_recv_lowat<S: AsRawFd>(socket: &S, bytes: u32) -> io::Result<()> {
+    let val: libc::c_int = bytes.try_into().unwrap_or(libc::c_int::MAX);
+    set_socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVLOWAT, val)
+}
+
+#[cfg(unix)]
+pub(crate) fn recv_lowat<S: AsRawFd>(socket: &S) -> io::Result<u32> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVLOWAT)?;
+    Ok(val as u32)
+}
+
+#[cfg(unix)]
+pub(crate) fn set_oobinline<S: AsRawFd>(socket: &S, oobinline: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(oobinline);
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_OOBINLINE,
+        val,
+    )
+}
+
+#[cfg(unix)]
+pub(crate) fn oobinline<S: AsRawFd>(socket: &S) -> io::Result<bool> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_OOBINLINE)?;
+    Ok(val != 0)
+}
+
+/// libc doesn't define `SIOCATMARK` for Linux and Android, on which it's
+/// `0x8905` on all architectures except MIPS.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[cfg(not(any(
+    target_arch = "mips",
+    target_arch = "mips32r6",
+    target_arch = "mips64",
+    target_arch = "mips64r6"
+)))]
+const SIOCATMARK: libc::c_int = 0x8905;
+/// `_IOR('s', 7, int)`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[cfg(any(
+    target_arch = "mips",
+    target_arch = "mips32r6",
+    target_arch = "mips64",
+    target_arch = "mips64r6"
+))]
+const SIOCATMARK: libc::c_int = 0x4004_7307;
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn at_mark<S: AsRawFd>(socket: &S) -> io::Result<bool> {
+    let mut at_mark: libc::c_int = 0;
+    // The type of the request differs between libcs.
+    syscall!(ioctl(socket.as_raw_fd(), SIOCATMARK as _, &mut at_mark))?;
+    Ok(at_mark != 0)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_notsent_lowat<S: AsRawFd>(socket: &S, bytes: u32) -> io::Result<()> {
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_NOTSENT_LOWAT,
+        bytes,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn notsent_lowat<S: AsRawFd>(socket: &S) -> io::Result<u32> {
+    socket_option(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_NOTSENT_LOWAT,
+    )
+}
+
+/// Get the `TCP_INFO` of `socket`, returns the number of bytes of `T` set by
+/// the OS, older kernels set less fields.
+///
+/// `T` must be a type for which all bit patterns are valid.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn tcp_info<S: AsRawFd, T>(socket: &S) -> io::Result<(T, usize)> {
+    socket_option_len(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_INFO)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_congestion<S: AsRawFd>(socket: &S, name: &str) -> io::Result<()> {
+    syscall!(setsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_CONGESTION,
+        name.as_ptr().cast(),
+        name.len() as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn congestion<S: AsRawFd>(socket: &S) -> io::Result<String> {
+    // `TCP_CA_NAME_MAX` in the kernel.
+    let name: [u8; 16] =
+        socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_CONGESTION)?;
+    let length = name.iter().position(|b| *b == 0).unwrap_or(name.len());
+    Ok(String::from_utf8_lossy(&name[..length]).into_owned())
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_cork<S: AsRawFd>(socket: &S, cork: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(cork);
+    set_socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_CORK, val)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn cork<S: AsRawFd>(socket: &S) -> io::Result<bool> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_CORK)?;
+    Ok(val != 0)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_quickack<S: AsRawFd>(socket: &S, quickack: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(quickack);
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_QUICKACK,
+        val,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn quickack<S: AsRawFd>(socket: &S) -> io::Result<bool> {
+    let val: libc::c_int =
+        socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_QUICKACK)?;
+    Ok(val != 0)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_defer_accept<S: AsRawFd>(
+    socket: &S,
+    timeout: Option<Duration>,
+) -> io::Result<()> {
+    let val: libc::c_int = match timeout {
+        // Zero disables the option.
+        Some(timeout) => secs_to_c_int(timeout),
+        None => 0,
+    };
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_DEFER_ACCEPT,
+        val,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn defer_accept<S: AsRawFd>(socket: &S) -> io::Result<Option<Duration>> {
+    let val: libc::c_int = socket_option(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_DEFER_ACCEPT,
+    )?;
+    Ok((val != 0).then(|| Duration::from_secs(val as u64)))
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_fastopen<S: AsRawFd>(socket: &S, queue_length: u32) -> io::Result<()> {
+    let val: libc::c_int = queue_length.try_into().unwrap_or(libc::c_int::MAX);
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_FASTOPEN,
+        val,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn fastopen<S: AsRawFd>(socket: &S) -> io::Result<u32> {
+    let val: libc::c_int =
+        socket_option(socket.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_FASTOPEN)?;
+    Ok(val as u32)
+}
+
+/// Connect `socket` to `addr` using TCP Fast Open, returning the number of
+/// bytes of `data` sent in the SYN.
+///
+/// Falls back to a regular connect if Fast Open is not supported or disabled.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn connect_with_data<S: AsRawFd>(
+    socket: &S,
+    addr: SocketAddr,
+    data: &[u8],
+) -> io::Result<usize> {
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+    match syscall!(sendto(
+        socket.as_raw_fd(),
+        data.as_ptr().cast(),
+        data.len(),
+        libc::MSG_FASTOPEN | libc::MSG_NOSIGNAL,
+        raw_addr.as_ptr(),
+        raw_addr_length,
+    )) {
+        Ok(n) => Ok(n as usize),
+        // No cookie for the peer yet, only a SYN (requesting a cookie) is sent.
+        Err(ref err) if err.raw_os_error() == Some(libc::EINPROGRESS) => Ok(0),
+        // Fast Open is not supported or disabled (in `net.ipv4.tcp_fastopen`).
+        Err(ref err) if err.raw_os_error() == Some(libc::EOPNOTSUPP) => {
+            connect(socket, addr).map(|()| 0)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn sendfile<S: AsRawFd>(
+    socket: &S,
+    file: &std::fs::File,
+    offset: u64,
+    len: usize,
+) -> io::Result<usize> {
+    let mut offset = libc::off64_t::try_from(offset)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "offset too large"))?;
+    syscall!(sendfile64(
+        socket.as_raw_fd(),
+        file.as_raw_fd(),
+        &mut offset,
+        len
+    ))
+    .map(|n| n as usize)
+}
+
 pub(crate) fn accept(listener: &net::TcpListener) -> io::Result<(net::TcpStream, SocketAddr)> {
     let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::uninit();
     let mut length = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
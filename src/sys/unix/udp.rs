@@ -8,7 +8,11 @@ use std::os::fd::{AsRawFd, FromRawFd};
 #[cfg(target_os = "hermit")]
 use std::os::hermit::io::{AsRawFd, FromRawFd};
 
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+use crate::sys::unix::net::set_socket_option;
 use crate::sys::unix::net::{new_ip_socket, socket_addr};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys::unix::net::{socket_option, to_socket_addr};
 
 pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
     let fd = new_ip_socket(addr, libc::SOCK_DGRAM)?;
@@ -20,6 +24,20 @@ pub fn bind(addr: SocketAddr) -> io::Result<net::UdpSocket> {
     Ok(socket)
 }
 
+/// Same as [`bind`], but sets `SO_REUSEPORT` before binding.
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+pub(crate) fn bind_reuseport(addr: SocketAddr) -> io::Result<net::UdpSocket> {
+    let fd = new_ip_socket(addr, libc::SOCK_DGRAM)?;
+    let socket = unsafe { net::UdpSocket::from_raw_fd(fd) };
+
+    let val: libc::c_int = 1;
+    set_socket_option(fd, libc::SOL_SOCKET, libc::SO_REUSEPORT, val)?;
+    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+    syscall!(bind(fd, raw_addr.as_ptr(), raw_addr_length))?;
+
+    Ok(socket)
+}
+
 pub(crate) fn only_v6(socket: &net::UdpSocket) -> io::Result<bool> {
     let mut optval: libc::c_int = 0;
     let mut optlen = mem::size_of::<libc::c_int>() as libc::socklen_t;
@@ -34,3 +52,236 @@ pub(crate) fn only_v6(socket: &net::UdpSocket) -> io::Result<bool> {
 
     Ok(optval != 0)
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_gro(socket: &net::UdpSocket, gro: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(gro);
+    set_socket_option(socket.as_raw_fd(), libc::SOL_UDP, libc::UDP_GRO, val)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn gro(socket: &net::UdpSocket) -> io::Result<bool> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_UDP, libc::UDP_GRO)?;
+    Ok(val != 0)
+}
+
+/// Send `buf` using `UDP_SEGMENT`, letting the kernel (or the NIC) split it
+/// into datagrams of `segment_size` bytes.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_segmented(
+    socket: &net::UdpSocket,
+    buf: &[u8],
+    addr: Option<SocketAddr>,
+    segment_size: u16,
+) -> io::Result<usize> {
+    // Aligned for `cmsghdr`.
+    let mut control = [0u64; 4];
+    let mut iov = libc::iovec {
+        iov_base: buf.as_ptr().cast_mut().cast(),
+        iov_len: buf.len(),
+    };
+    // SAFETY: all zeroes is a valid `msghdr`.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    let raw_addr = addr.map(|addr| socket_addr(&addr));
+    if let Some((raw_addr, raw_addr_length)) = &raw_addr {
+        msg.msg_name = raw_addr.as_ptr().cast_mut().cast();
+        msg.msg_namelen = *raw_addr_length;
+    }
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    // SAFETY: `CMSG_SPACE` is safe to call.
+    msg.msg_controllen = unsafe { libc::CMSG_SPACE(mem::size_of::<u16>() as _) } as _;
+    // SAFETY: `msg_control` points to `control`, which is large enough for a
+    // single `u16` control message.
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_UDP;
+        (*cmsg).cmsg_type = libc::UDP_SEGMENT;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(mem::size_of::<u16>() as _) as _;
+        libc::CMSG_DATA(cmsg)
+            .cast::<u16>()
+            .write_unaligned(segment_size);
+    }
+    syscall!(sendmsg(socket.as_raw_fd(), &msg, 0)).map(|n| n as usize)
+}
+
+/// Receive into `buf`, returning the segment size reported in the `UDP_GRO`
+/// control message if multiple datagrams were coalesced.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_from_segmented(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+) -> io::Result<(usize, SocketAddr, Option<usize>)> {
+    // Aligned for `cmsghdr`.
+    let mut control = [0u64; 4];
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr().cast(),
+        iov_len: buf.len(),
+    };
+    // SAFETY: all zeroes is a valid `sockaddr_storage` and `msghdr`.
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_name = (&mut storage as *mut libc::sockaddr_storage).cast();
+    msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = mem::size_of_val(&control) as _;
+    let n = syscall!(recvmsg(socket.as_raw_fd(), &mut msg, 0))?;
+    // SAFETY: `recvmsg` initialised the address.
+    let addr = unsafe { to_socket_addr(&storage) }?;
+
+    let mut segment_size = None;
+    // SAFETY: `msg` is initialised by `recvmsg` above.
+    let mut cmsg = unsafe { libc::CMSG_FIRSTHDR(&msg) };
+    while !cmsg.is_null() {
+        // SAFETY: `cmsg` is not null and points into `control`.
+        let (level, kind) = unsafe { ((*cmsg).cmsg_level, (*cmsg).cmsg_type) };
+        if level == libc::SOL_UDP && kind == libc::UDP_GRO {
+            // SAFETY: the data of this message is an `int`.
+            let size = unsafe { libc::CMSG_DATA(cmsg).cast::<libc::c_int>().read_unaligned() };
+            segment_size = Some(size as usize);
+        }
+        // SAFETY: same as `CMSG_FIRSTHDR` above.
+        cmsg = unsafe { libc::CMSG_NXTHDR(&msg, cmsg) };
+    }
+    Ok((n as usize, addr, segment_size))
+}
+
+/// Set an option to receive a control message, `v4` is set for IPv4 sockets,
+/// both `v4` and `v6` for IPv6 sockets as those can also receive IPv4
+/// datagrams.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_recv_option(
+    socket: &net::UdpSocket,
+    v4: libc::c_int,
+    v6: libc::c_int,
+    enable: bool,
+) -> io::Result<()> {
+    let val: libc::c_int = i32::from(enable);
+    if socket.local_addr()?.is_ipv6() {
+        set_socket_option(socket.as_raw_fd(), libc::IPPROTO_IPV6, v6, val)?;
+    }
+    set_socket_option(socket.as_raw_fd(), libc::IPPROTO_IP, v4, val)
+}
+
+/// Get an option set by [`set_recv_option`].
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn recv_option(socket: &net::UdpSocket, v4: libc::c_int, v6: libc::c_int) -> io::Result<bool> {
+    let val: libc::c_int = if socket.local_addr()?.is_ipv6() {
+        socket_option(socket.as_raw_fd(), libc::IPPROTO_IPV6, v6)?
+    } else {
+        socket_option(socket.as_raw_fd(), libc::IPPROTO_IP, v4)?
+    };
+    Ok(val != 0)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_pktinfo(socket: &net::UdpSocket, enable: bool) -> io::Result<()> {
+    set_recv_option(socket, libc::IP_PKTINFO, libc::IPV6_RECVPKTINFO, enable)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_pktinfo(socket: &net::UdpSocket) -> io::Result<bool> {
+    recv_option(socket, libc::IP_PKTINFO, libc::IPV6_RECVPKTINFO)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_ttl(socket: &net::UdpSocket, enable: bool) -> io::Result<()> {
+    set_recv_option(socket, libc::IP_RECVTTL, libc::IPV6_RECVHOPLIMIT, enable)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_ttl(socket: &net::UdpSocket) -> io::Result<bool> {
+    recv_option(socket, libc::IP_RECVTTL, libc::IPV6_RECVHOPLIMIT)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_timestamp(socket: &net::UdpSocket, enable: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(enable);
+    set_socket_option(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        libc::SO_TIMESTAMPNS,
+        val,
+    )
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_timestamp(socket: &net::UdpSocket) -> io::Result<bool> {
+    let val: libc::c_int =
+        socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_TIMESTAMPNS)?;
+    Ok(val != 0)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn set_recv_overflow(socket: &net::UdpSocket, enable: bool) -> io::Result<()> {
+    let val: libc::c_int = i32::from(enable);
+    set_socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RXQ_OVFL, val)
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_overflow(socket: &net::UdpSocket) -> io::Result<bool> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RXQ_OVFL)?;
+    Ok(val != 0)
+}
+
+/// Send `buf` along with the encoded control messages in `control`, which
+/// must be aligned for `cmsghdr`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn send_msg(
+    socket: &net::UdpSocket,
+    buf: &[u8],
+    addr: Option<SocketAddr>,
+    control: &[u8],
+) -> io::Result<usize> {
+    let mut iov = libc::iovec {
+        iov_base: buf.as_ptr().cast_mut().cast(),
+        iov_len: buf.len(),
+    };
+    // SAFETY: all zeroes is a valid `msghdr`.
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    let raw_addr = addr.map(|addr| socket_addr(&addr));
+    if let Some((raw_addr, raw_addr_length)) = &raw_addr {
+        msg.msg_name = raw_addr.as_ptr().cast_mut().cast();
+        msg.msg_namelen = *raw_addr_length;
+    }
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    if !control.is_empty() {
+        msg.msg_control = control.as_ptr().cast_mut().cast();
+        msg.msg_controllen = control.len() as _;
+    }
+    syscall!(sendmsg(socket.as_raw_fd(), &msg, 0)).map(|n| n as usize)
+}
+
+/// Receive into `buf` and the control messages into `control`, which must be
+/// aligned for `cmsghdr`. Returns the number of bytes read, the source
+/// address, the length of the control messages and whether those were
+/// truncated.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn recv_msg(
+    socket: &net::UdpSocket,
+    buf: &mut [u8],
+    control: &mut [u8],
+) -> io::Result<(usize, SocketAddr, usize, bool)> {
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr().cast(),
+        iov_len: buf.len(),
+    };
+    // SAFETY: all zeroes is a valid `sockaddr_storage` and `msghdr`.
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    msg.msg_name = (&mut storage as *mut libc::sockaddr_storage).cast();
+    msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = control.len() as _;
+    let n = syscall!(recvmsg(socket.as_raw_fd(), &mut msg, 0))?;
+    // SAFETY: `recvmsg` initialised the address.
+    let addr = unsafe { to_socket_addr(&storage) }?;
+    let truncated = msg.msg_flags & libc::MSG_CTRUNC != 0;
+    Ok((n as usize, addr, msg.msg_controllen as usize, truncated))
+}
@@ -1,9 +1,13 @@
 use std::io;
+use std::mem::{self, MaybeUninit};
 use std::os::fd::{AsRawFd, FromRawFd};
 use std::os::unix::net::{self, SocketAddr};
+use std::ptr;
 
+use crate::net::{MsgFlags, RecvDatagram, SendDatagram};
+use crate::sys::unix::msg::{recv_many_with, send_many_with};
 use crate::sys::unix::net::new_socket;
-use crate::sys::unix::uds::unix_addr;
+use crate::sys::unix::uds::{to_socket_addr, unix_addr};
 
 pub(crate) fn bind_addr(address: &SocketAddr) -> io::Result<net::UnixDatagram> {
     let socket = unbound()?;
@@ -23,3 +27,89 @@ pub(crate) fn unbound() -> io::Result<net::UnixDatagram> {
 pub(crate) fn pair() -> io::Result<(net::UnixDatagram, net::UnixDatagram)> {
     super::pair(libc::SOCK_DGRAM)
 }
+
+pub(crate) fn send_to_with_flags(
+    socket: &net::UnixDatagram,
+    buf: &[u8],
+    address: &SocketAddr,
+    flags: MsgFlags,
+) -> io::Result<usize> {
+    let (unix_address, addrlen) = unix_addr(address);
+    let sockaddr = &unix_address as *const libc::sockaddr_un as *const libc::sockaddr;
+    syscall!(sendto(
+        socket.as_raw_fd(),
+        buf.as_ptr().cast(),
+        buf.len(),
+        flags.as_raw(),
+        sockaddr,
+        addrlen,
+    ))
+    .map(|n| n as usize)
+}
+
+pub(crate) fn recv_from_with_flags(
+    socket: &net::UnixDatagram,
+    buf: &mut [u8],
+    flags: MsgFlags,
+) -> io::Result<(usize, SocketAddr)> {
+    recv_from(socket, buf.as_mut_ptr().cast(), buf.len(), flags)
+}
+
+pub(crate) fn recv_from_uninit(
+    socket: &net::UnixDatagram,
+    buf: &mut [MaybeUninit<u8>],
+    flags: MsgFlags,
+) -> io::Result<(usize, SocketAddr)> {
+    recv_from(socket, buf.as_mut_ptr().cast(), buf.len(), flags)
+}
+
+fn recv_from(
+    socket: &net::UnixDatagram,
+    buf: *mut libc::c_void,
+    len: usize,
+    flags: MsgFlags,
+) -> io::Result<(usize, SocketAddr)> {
+    // SAFETY: `libc::sockaddr_un` zero filled is properly initialized.
+    let mut sockaddr = unsafe { mem::zeroed::<libc::sockaddr_un>() };
+    let mut socklen = mem::size_of_val(&sockaddr) as libc::socklen_t;
+    let n = syscall!(recvfrom(
+        socket.as_raw_fd(),
+        buf,
+        len,
+        flags.as_raw(),
+        &mut sockaddr as *mut libc::sockaddr_un as *mut libc::sockaddr,
+        &mut socklen,
+    ))?;
+    let address = to_socket_addr(&sockaddr, socklen)?;
+    Ok((n as usize, address))
+}
+
+pub(crate) fn send_many(
+    socket: &net::UnixDatagram,
+    msgs: &[SendDatagram<'_, SocketAddr>],
+) -> io::Result<usize> {
+    send_many_with(socket.as_raw_fd(), msgs, |address, storage| {
+        let (unix_address, addrlen) = unix_addr(address);
+        // SAFETY: `sockaddr_storage` is large enough and suitably aligned to
+        // hold a `sockaddr_un`.
+        unsafe {
+            ptr::write(
+                (storage as *mut libc::sockaddr_storage).cast(),
+                unix_address,
+            )
+        };
+        addrlen
+    })
+}
+
+pub(crate) fn recv_many(
+    socket: &net::UnixDatagram,
+    msgs: &mut [RecvDatagram<'_, SocketAddr>],
+) -> io::Result<usize> {
+    recv_many_with(socket.as_raw_fd(), msgs, |storage, socklen| {
+        // SAFETY: `sockaddr_storage` is large enough and suitably aligned to
+        // hold a `sockaddr_un`.
+        let sockaddr = unsafe { &*(storage as *const libc::sockaddr_storage).cast() };
+        to_socket_addr(sockaddr, socklen)
+    })
+}
@@ -1,13 +1,10 @@
-use std::ffi::OsStr;
 use std::os::fd::{AsRawFd, FromRawFd};
-use std::os::unix::ffi::OsStrExt;
 use std::os::unix::net::{self, SocketAddr};
-use std::path::Path;
 use std::{io, mem};
 
 use crate::net::UnixStream;
 use crate::sys::unix::net::new_socket;
-use crate::sys::unix::uds::{path_offset, unix_addr};
+use crate::sys::unix::uds::{to_socket_addr, unix_addr};
 
 pub(crate) fn bind_addr(address: &SocketAddr) -> io::Result<net::UnixListener> {
     let fd = new_socket(libc::AF_UNIX, libc::SOCK_STREAM)?;
@@ -133,20 +130,6 @@ pub(crate) fn accept(listener: &net::UnixListener) -> io::Result<(UnixStream, So
 
     let socket = socket.map(UnixStream::from_std)?;
 
-    #[allow(unused_mut)] // See below.
-    let mut path_len = socklen as usize - path_offset(&sockaddr);
-    // On FreeBSD and Darwin, it returns a length of 14/16, but an unnamed (all
-    // zero) address. Map that to a length of 0 to match other OS.
-    if sockaddr.sun_path[0] == 0 {
-        path_len = 0;
-    }
-    // SAFETY: going from i8 to u8 is fine in this context.
-    let mut path =
-        unsafe { &*(&sockaddr.sun_path[..path_len] as *const [libc::c_char] as *const [u8]) };
-    // Remove last null as `SocketAddr::from_pathname` doesn't accept it.
-    if let Some(0) = path.last() {
-        path = &path[..path.len() - 1];
-    }
-    let address = SocketAddr::from_pathname(Path::new(OsStr::from_bytes(path)))?;
+    let address = to_socket_addr(&sockaddr, socklen)?;
     Ok((socket, address))
 }
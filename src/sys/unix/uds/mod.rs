@@ -1,3 +1,4 @@
+use std::ffi::OsStr;
 #[cfg(target_os = "android")]
 use std::os::android::net::SocketAddrExt;
 #[cfg(target_os = "linux")]
@@ -5,6 +6,7 @@ use std::os::linux::net::SocketAddrExt;
 use std::os::unix::ffi::OsStrExt;
 use std::os::unix::io::FromRawFd;
 use std::os::unix::net::SocketAddr;
+use std::path::Path;
 use std::{io, mem, ptr};
 
 pub(crate) mod datagram;
@@ -81,6 +83,29 @@ fn unix_addr(address: &SocketAddr) -> (libc::sockaddr_un, libc::socklen_t) {
     (sockaddr, addrlen as _)
 }
 
+/// Converts a `sockaddr_un` returned by the OS into a Rust `SocketAddr`.
+fn to_socket_addr(
+    sockaddr: &libc::sockaddr_un,
+    socklen: libc::socklen_t,
+) -> io::Result<SocketAddr> {
+    #[allow(unused_mut)] // See below.
+    // Some OS return a zero length for unnamed addresses.
+    let mut path_len = (socklen as usize).saturating_sub(path_offset(sockaddr));
+    // On FreeBSD and Darwin, it returns a length of 14/16, but an unnamed (all
+    // zero) address. Map that to a length of 0 to match other OS.
+    if sockaddr.sun_path[0] == 0 {
+        path_len = 0;
+    }
+    // SAFETY: going from i8 to u8 is fine in this context.
+    let mut path =
+        unsafe { &*(&sockaddr.sun_path[..path_len] as *const [libc::c_char] as *const [u8]) };
+    // Remove last null as `SocketAddr::from_pathname` doesn't accept it.
+    if let Some(0) = path.last() {
+        path = &path[..path.len() - 1];
+    }
+    SocketAddr::from_pathname(Path::new(OsStr::from_bytes(path)))
+}
+
 fn pair<T>(flags: libc::c_int) -> io::Result<(T, T)>
 where
     T: FromRawFd,
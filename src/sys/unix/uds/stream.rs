@@ -1,8 +1,8 @@
 use std::io;
-use std::os::fd::FromRawFd;
+use std::os::fd::{AsRawFd, FromRawFd};
 use std::os::unix::net::{self, SocketAddr};
 
-use crate::sys::unix::net::new_socket;
+use crate::sys::unix::net::{new_socket, set_socket_option, socket_option};
 use crate::sys::unix::uds::unix_addr;
 
 pub(crate) fn connect_addr(address: &SocketAddr) -> io::Result<net::UnixStream> {
@@ -23,3 +23,13 @@ pub(crate) fn connect_addr(address: &SocketAddr) -> io::Result<net::UnixStream>
 pub(crate) fn pair() -> io::Result<(net::UnixStream, net::UnixStream)> {
     super::pair(libc::SOCK_STREAM)
 }
+
+pub(crate) fn set_recv_lowat(socket: &net::UnixStream, bytes: u32) -> io::Result<()> {
+    let val: libc::c_int = bytes.try_into().unwrap_or(libc::c_int::MAX);
+    set_socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVLOWAT, val)
+}
+
+pub(crate) fn recv_lowat(socket: &net::UnixStream) -> io::Result<u32> {
+    let val: libc::c_int = socket_option(socket.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVLOWAT)?;
+    Ok(val as u32)
+}
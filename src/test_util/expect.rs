@@ -0,0 +1,144 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::event::Event;
+use crate::test_util::Readiness;
+use crate::{Events, Poll, Token};
+
+/// An event that is expected to be returned by [`Poll::poll`], see
+/// [`expect_events`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ExpectEvent {
+    token: Token,
+    readiness: Readiness,
+}
+
+impl ExpectEvent {
+    /// Create a new `ExpectEvent`, expecting an event for `token` with (at
+    /// least) `readiness`.
+    ///
+    /// `readiness` can be an [`Interest`], e.g. `Interest::READABLE`, or a
+    /// [`Readiness`] to also match other readiness, e.g. read closed.
+    ///
+    /// [`Interest`]: crate::Interest
+    pub fn new<R>(token: Token, readiness: R) -> ExpectEvent
+    where
+        R: Into<Readiness>,
+    {
+        ExpectEvent {
+            token,
+            readiness: readiness.into(),
+        }
+    }
+
+    /// Returns the token of the expected event.
+    pub const fn token(&self) -> Token {
+        self.token
+    }
+
+    /// Returns the readiness of the expected event.
+    pub const fn readiness(&self) -> Readiness {
+        self.readiness
+    }
+
+    /// Returns true if `event` matches the expected event.
+    pub fn matches(&self, event: &Event) -> bool {
+        event.token() == self.token && self.readiness.matches(event)
+    }
+}
+
+/// Poll `poll` until all `expected` events are returned.
+///
+/// Events that are not expected are ignored, as most OSs can return spurious
+/// events. Polls at most three times, waiting at most 500 milliseconds each
+/// time.
+///
+/// # Panics
+///
+/// This panics if not all expected events are returned or if polling returns
+/// an error. The panic message contains the events that were not found and all
+/// the received events, including the OS specific details.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "net", doc = "```")]
+#[cfg_attr(not(feature = "net"), doc = "```ignore")]
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::UdpSocket;
+/// use mio::test_util::{expect_events, ExpectEvent};
+/// use mio::{Events, Interest, Poll, Token};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let mut socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+/// poll.registry()
+///     .register(&mut socket, Token(0), Interest::WRITABLE)?;
+///
+/// expect_events(
+///     &mut poll,
+///     &mut events,
+///     vec![ExpectEvent::new(Token(0), Interest::WRITABLE)],
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+#[track_caller]
+pub fn expect_events(poll: &mut Poll, events: &mut Events, mut expected: Vec<ExpectEvent>) {
+    let mut received = Vec::new();
+    // In a lot of calls we expect more then one event, but it could be that
+    // poll returns the first event only in a single call. To be a bit more
+    // lenient we'll poll a couple of times.
+    for _ in 0..3 {
+        poll.poll(events, Some(Duration::from_millis(500)))
+            .expect("unable to poll");
+
+        for event in events.iter() {
+            if let Some(index) = expected.iter().position(|e| e.matches(event)) {
+                expected.swap_remove(index);
+            }
+            received.push(event.clone());
+        }
+
+        if expected.is_empty() {
+            return;
+        }
+    }
+
+    let mut msg = String::from("the following expected events were not found:");
+    for event in &expected {
+        let _ = write!(msg, "\n  {event:?}");
+    }
+    msg.push_str("\nreceived events:");
+    write_events(&mut msg, &received);
+    panic!("{msg}");
+}
+
+/// Poll `poll` once, waiting 50 milliseconds, expecting no events.
+///
+/// # Panics
+///
+/// This panics if any events are returned or if polling returns an error. The
+/// panic message contains the received events, including the OS specific
+/// details.
+#[track_caller]
+pub fn expect_no_events(poll: &mut Poll, events: &mut Events) {
+    poll.poll(events, Some(Duration::from_millis(50)))
+        .expect("unable to poll");
+    if !events.is_empty() {
+        let received: Vec<Event> = events.iter().cloned().collect();
+        let mut msg = String::from("expected no events, received events:");
+        write_events(&mut msg, &received);
+        panic!("{msg}");
+    }
+}
+
+fn write_events(msg: &mut String, events: &[Event]) {
+    if events.is_empty() {
+        msg.push_str(" (none)");
+    }
+    for event in events {
+        let _ = write!(msg, "\n{event:#?}");
+    }
+}
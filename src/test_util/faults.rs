@@ -0,0 +1,460 @@
+use std::collections::VecDeque;
+#[cfg(any(feature = "net", feature = "os-ext"))]
+use std::os::fd::RawFd;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::{fmt, io};
+
+use crate::{sys, Poll};
+#[cfg(any(feature = "net", feature = "os-ext"))]
+use crate::{Interest, Token};
+
+/// Handle to a `Poll` that injects faults.
+///
+/// The `Poll` created by [`Faults::new`] uses the OS selector like any other
+/// `Poll`, so real event sources such as [`TcpStream`] can be used with it.
+/// However on top of that it injects faults into the operations on the OS
+/// selector, e.g. [`Poll::poll`] returning an [`Interrupted`] error or
+/// [`Registry::register`] failing with `ENOMEM`. This can be used to test if an
+/// event loop copes with these faults.
+///
+/// Faults can be injected in two ways:
+///  * Using a script, see [`Faults::script`], which determines the faults for
+///    the next operations.
+///  * Using a probability, see [`Faults::set_probability`], which randomly
+///    injects faults. The randomness is determined by the seed passed to
+///    [`Faults::new`], making the faults reproducible.
+///
+/// If both are set for an operation the script takes precedence.
+///
+/// [`TcpStream`]: crate::net::TcpStream
+/// [`Interrupted`]: io::ErrorKind::Interrupted
+/// [`Registry::register`]: crate::Registry::register
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io;
+/// use std::time::Duration;
+///
+/// use mio::test_util::{Fault, Faults, Operation};
+/// use mio::Events;
+///
+/// let (mut poll, faults) = Faults::new(0)?;
+/// let mut events = Events::with_capacity(8);
+///
+/// // The first call is interrupted, the second call is not affected.
+/// faults.script(Operation::Poll, [Some(Fault::Interrupted), None]);
+///
+/// let err = poll.poll(&mut events, Some(Duration::ZERO)).unwrap_err();
+/// assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+/// poll.poll(&mut events, Some(Duration::ZERO))?;
+///
+/// // From now on a third of the calls is interrupted.
+/// faults.set_probability(Operation::Poll, Fault::Interrupted, 0.33);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Faults {
+    shared: Arc<FaultState>,
+}
+
+/// Operation on the selector that faults can be injected into.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// [`Poll::poll`].
+    Poll,
+    /// [`Registry::register`].
+    ///
+    /// [`Registry::register`]: crate::Registry::register
+    Register,
+    /// [`Registry::reregister`].
+    ///
+    /// [`Registry::reregister`]: crate::Registry::reregister
+    Reregister,
+    /// [`Registry::deregister`].
+    ///
+    /// [`Registry::deregister`]: crate::Registry::deregister
+    Deregister,
+}
+
+/// Fault injected into an [`Operation`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Fault {
+    /// Return an `EINTR` error, without waiting for events.
+    ///
+    /// Can be injected into [`Operation::Poll`].
+    Interrupted,
+    /// Return without any events, without waiting for events.
+    ///
+    /// Can be injected into [`Operation::Poll`].
+    SpuriousWakeup,
+    /// Return one of the events twice, if the `Events` has enough capacity.
+    ///
+    /// Can be injected into [`Operation::Poll`].
+    Duplicate,
+    /// Shuffle the order of the events.
+    ///
+    /// Can be injected into [`Operation::Poll`].
+    Reorder,
+    /// Return an `ENOMEM` error, without registering the event source.
+    ///
+    /// Can be injected into [`Operation::Register`] and
+    /// [`Operation::Reregister`].
+    OutOfMemory,
+    /// Return an `ENOSPC` error, without registering the event source.
+    ///
+    /// Can be injected into [`Operation::Register`].
+    NoSpace,
+    /// Return an `ENOENT` error, without changing the registration of the
+    /// event source.
+    ///
+    /// Can be injected into [`Operation::Reregister`] and
+    /// [`Operation::Deregister`].
+    NotFound,
+}
+
+impl Fault {
+    /// Returns true if the fault can be injected into `op`.
+    const fn applies_to(self, op: Operation) -> bool {
+        match self {
+            Fault::Interrupted | Fault::SpuriousWakeup | Fault::Duplicate | Fault::Reorder => {
+                matches!(op, Operation::Poll)
+            }
+            Fault::OutOfMemory => matches!(op, Operation::Register | Operation::Reregister),
+            Fault::NoSpace => matches!(op, Operation::Register),
+            Fault::NotFound => matches!(op, Operation::Reregister | Operation::Deregister),
+        }
+    }
+
+    fn error(self) -> io::Error {
+        let errno = match self {
+            Fault::Interrupted => libc::EINTR,
+            Fault::OutOfMemory => libc::ENOMEM,
+            Fault::NoSpace => libc::ENOSPC,
+            Fault::NotFound => libc::ENOENT,
+            Fault::SpuriousWakeup | Fault::Duplicate | Fault::Reorder => {
+                unreachable!("fault is not an error")
+            }
+        };
+        io::Error::from_raw_os_error(errno)
+    }
+}
+
+impl Faults {
+    /// Create a new `Poll` that injects faults and the handle to control it.
+    ///
+    /// `seed` is used for the faults injected based on a probability, using the
+    /// same seed injects the same faults (given the same operations).
+    pub fn new(seed: u64) -> io::Result<(Poll, Faults)> {
+        let shared = Arc::new(FaultState {
+            inner: Mutex::new(State {
+                // Xorshift can't use a zero state.
+                rng: if seed == 0 {
+                    0x9E37_79B9_7F4A_7C15
+                } else {
+                    seed
+                },
+                scripts: Vec::new(),
+                probabilities: Vec::new(),
+                injected: Vec::new(),
+            }),
+        });
+        let poll = Poll::new_faults(Arc::clone(&shared))?;
+        Ok((poll, Faults { shared }))
+    }
+
+    /// Script the faults for the next calls of `op`.
+    ///
+    /// Each item in `faults` determines the fault for one call, where `None`
+    /// means no fault is injected. The items are appended to the existing
+    /// script for `op`. Once the script is exhausted faults are injected based
+    /// on the probabilities again.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if any of the faults can't be injected into `op`.
+    pub fn script<I>(&self, op: Operation, faults: I)
+    where
+        I: IntoIterator<Item = Option<Fault>>,
+    {
+        let mut state = self.shared.lock();
+        let script = match state.scripts.iter_mut().find(|(o, _)| *o == op) {
+            Some((_, script)) => script,
+            None => {
+                state.scripts.push((op, VecDeque::new()));
+                &mut state.scripts.last_mut().unwrap().1
+            }
+        };
+        for fault in faults {
+            if let Some(fault) = fault {
+                assert!(
+                    fault.applies_to(op),
+                    "fault {fault:?} can't be injected into {op:?}"
+                );
+            }
+            script.push_back(fault);
+        }
+    }
+
+    /// Set the probability, between 0.0 and 1.0, of `fault` being injected into
+    /// a call of `op`.
+    ///
+    /// Faults are checked in the order they were first set, at most one fault
+    /// is injected per call. Setting the probability to 0.0 disables the fault.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `fault` can't be injected into `op` or if
+    /// `probability` is not between 0.0 and 1.0.
+    pub fn set_probability(&self, op: Operation, fault: Fault, probability: f64) {
+        assert!(
+            fault.applies_to(op),
+            "fault {fault:?} can't be injected into {op:?}"
+        );
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "invalid probability: {probability}"
+        );
+        let mut state = self.shared.lock();
+        match state
+            .probabilities
+            .iter_mut()
+            .find(|(o, f, _)| *o == op && *f == fault)
+        {
+            Some((_, _, p)) => *p = probability,
+            None => state.probabilities.push((op, fault, probability)),
+        }
+    }
+
+    /// Remove all scripted faults and probabilities, no more faults will be
+    /// injected.
+    pub fn clear(&self) {
+        let mut state = self.shared.lock();
+        state.scripts.clear();
+        state.probabilities.clear();
+    }
+
+    /// Returns all the faults injected so far, in order.
+    pub fn injected(&self) -> Vec<(Operation, Fault)> {
+        self.shared.lock().injected.clone()
+    }
+}
+
+impl fmt::Debug for Faults {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Faults").finish()
+    }
+}
+
+/// State shared between [`Faults`] and the `Poll` injecting the faults.
+pub(crate) struct FaultState {
+    inner: Mutex<State>,
+}
+
+struct State {
+    /// State of the xorshift random number generator.
+    rng: u64,
+    scripts: Vec<(Operation, VecDeque<Option<Fault>>)>,
+    probabilities: Vec<(Operation, Fault, f64)>,
+    injected: Vec<(Operation, Fault)>,
+}
+
+impl State {
+    fn next_u64(&mut self) -> u64 {
+        // Xorshift64*, see <https://en.wikipedia.org/wiki/Xorshift#xorshift*>.
+        let mut x = self.rng;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.rng = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Returns a random number in `0..n`.
+    fn next_below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Returns a random number in `0.0..1.0`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Determine the fault to inject into `op`, if any. The fault is only
+    /// recorded once it's actually applied, see [`State::applied`].
+    fn fault(&mut self, op: Operation) -> Option<Fault> {
+        let scripted = self
+            .scripts
+            .iter_mut()
+            .find(|(o, _)| *o == op)
+            .and_then(|(_, script)| script.pop_front());
+        match scripted {
+            Some(fault) => fault,
+            None => {
+                let mut fault = None;
+                for i in 0..self.probabilities.len() {
+                    let (o, f, probability) = self.probabilities[i];
+                    if o == op && self.next_f64() < probability {
+                        fault = Some(f);
+                        break;
+                    }
+                }
+                fault
+            }
+        }
+    }
+
+    /// Record that `fault` was injected into `op`.
+    fn applied(&mut self, op: Operation, fault: Fault) {
+        self.injected.push((op, fault));
+    }
+}
+
+impl FaultState {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Inject a fault into a call to `select`, if any.
+    pub(crate) fn select<F>(&self, events: &mut sys::Events, select: F) -> io::Result<()>
+    where
+        F: FnOnce(&mut sys::Events) -> io::Result<()>,
+    {
+        let fault = self.lock().fault(Operation::Poll);
+        match fault {
+            Some(Fault::Interrupted) => {
+                events.clear();
+                self.lock().applied(Operation::Poll, Fault::Interrupted);
+                return Err(Fault::Interrupted.error());
+            }
+            Some(Fault::SpuriousWakeup) => {
+                events.clear();
+                self.lock().applied(Operation::Poll, Fault::SpuriousWakeup);
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        select(events)?;
+
+        match fault {
+            Some(Fault::Duplicate) if !events.is_empty() && events.len() < events.capacity() => {
+                let mut state = self.lock();
+                let index = state.next_below(events.len());
+                // Not all `sys::Event`s implement `Copy`.
+                #[allow(clippy::clone_on_copy)]
+                let event = events[index].clone();
+                events.insert(index + 1, event);
+                state.applied(Operation::Poll, Fault::Duplicate);
+            }
+            Some(Fault::Reorder) if events.len() > 1 => {
+                // Fisher–Yates shuffle.
+                let mut state = self.lock();
+                for i in (1..events.len()).rev() {
+                    let j = state.next_below(i + 1);
+                    events.swap(i, j);
+                }
+                state.applied(Operation::Poll, Fault::Reorder);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Returns the error for `op`, if a fault is injected.
+    #[cfg(any(feature = "net", feature = "os-ext"))]
+    fn check(&self, op: Operation) -> io::Result<()> {
+        let mut state = self.lock();
+        match state.fault(op) {
+            Some(fault) => {
+                state.applied(op, fault);
+                Err(fault.error())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Handle to the `sys::Selector` of a `Registry`, injecting faults into
+/// (re|de)registering file descriptors if the `Registry` belongs to a `Poll`
+/// created by [`Faults::new`].
+///
+/// Returned by `Registry::fault_selector`. Faults for `select` are injected by [`Poll::poll`] using [`FaultState::select`].
+pub(crate) struct Selector<'a> {
+    #[cfg_attr(not(any(feature = "net", feature = "os-ext")), allow(dead_code))]
+    selector: &'a sys::Selector,
+    #[cfg_attr(not(any(feature = "net", feature = "os-ext")), allow(dead_code))]
+    faults: Option<&'a FaultState>,
+}
+
+impl<'a> Selector<'a> {
+    pub(crate) const fn new(
+        selector: &'a sys::Selector,
+        faults: Option<&'a FaultState>,
+    ) -> Selector<'a> {
+        Selector { selector, faults }
+    }
+
+    #[cfg(any(feature = "net", feature = "os-ext"))]
+    fn check(&self, op: Operation) -> io::Result<()> {
+        match self.faults {
+            Some(faults) => faults.check(op),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(any(feature = "net", feature = "os-ext"))]
+    pub(crate) fn register(&self, fd: RawFd, token: Token, interests: Interest) -> io::Result<()> {
+        self.check(Operation::Register)?;
+        self.selector.register(fd, token, interests)
+    }
+
+    /// Same as [`Selector::register`], used by the `IoSourceState` of the
+    /// `poll(2)` and event ports selectors.
+    #[cfg(all(
+        any(feature = "net", feature = "os-ext"),
+        any(
+            mio_unsupported_force_poll_poll,
+            target_os = "aix",
+            target_os = "espidf",
+            target_os = "nuttx",
+            target_os = "fuchsia",
+            target_os = "haiku",
+            target_os = "hurd",
+            target_os = "nto",
+            target_os = "vita",
+            target_os = "cygwin",
+            target_os = "horizon",
+            target_os = "solaris",
+        )
+    ))]
+    pub(crate) fn register_internal(
+        &self,
+        fd: RawFd,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<Arc<sys::RegistrationRecord>> {
+        self.check(Operation::Register)?;
+        self.selector.register_internal(fd, token, interests)
+    }
+
+    #[cfg(any(feature = "net", feature = "os-ext"))]
+    pub(crate) fn reregister(
+        &self,
+        fd: RawFd,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.check(Operation::Reregister)?;
+        self.selector.reregister(fd, token, interests)
+    }
+
+    #[cfg(any(feature = "net", feature = "os-ext"))]
+    pub(crate) fn deregister(&self, fd: RawFd) -> io::Result<()> {
+        self.check(Operation::Deregister)?;
+        self.selector.deregister(fd)
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::{HashMap, VecDeque};
+use std::os::fd::RawFd;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use std::{fmt, io};
+
+use crate::test_util::Readiness;
+use crate::{sys, Interest, Poll, Token};
+
+/// Handle to a mock [`Poll`].
+///
+/// A mock `Poll` doesn't wait for events from the OS, instead the events it
+/// returns are injected using this handle. This allows for deterministic
+/// testing of code using Mio.
+///
+/// Event sources, such as [`TcpStream`], can still be registered with the mock
+/// `Poll` like usual. These registrations are still passed to the OS selector,
+/// but they are recorded and can be inspected using [`registrations`] and
+/// [`calls`].
+///
+/// The mock `Poll` has a virtual clock, which only moves forward if
+/// [`Poll::poll`] is called with a timeout while no events are ready, or if
+/// it's moved manually using [`advance`]. Events can be scheduled for a moment
+/// on this virtual clock using [`inject_after`].
+///
+/// [`TcpStream`]: crate::net::TcpStream
+/// [`registrations`]: Mock::registrations
+/// [`calls`]: Mock::calls
+/// [`advance`]: Mock::advance
+/// [`inject_after`]: Mock::inject_after
+///
+/// # Notes
+///
+/// Calling [`Poll::poll`] without a timeout when no events are ready or
+/// scheduled returns an error, as it would block forever.
+///
+/// Events from a [`Waker`] are **not** returned by a mock `Poll`, inject them
+/// manually.
+///
+/// [`Waker`]: crate::Waker
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::time::Duration;
+///
+/// use mio::test_util::{Mock, Readiness};
+/// use mio::{Events, Token};
+///
+/// let (mut poll, mock) = Mock::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// mock.inject(Token(0), Readiness::READABLE);
+/// mock.inject_after(Duration::from_secs(10), Token(1), Readiness::WRITABLE);
+///
+/// poll.poll(&mut events, None)?;
+/// let event = events.iter().next().unwrap();
+/// assert_eq!(event.token(), Token(0));
+/// assert!(event.is_readable());
+///
+/// // Not enough time passed for the second event.
+/// poll.poll(&mut events, Some(Duration::from_secs(5)))?;
+/// assert!(events.is_empty());
+/// assert_eq!(mock.elapsed(), Duration::from_secs(5));
+///
+/// // Polling without a timeout moves the clock to the next event.
+/// poll.poll(&mut events, None)?;
+/// let event = events.iter().next().unwrap();
+/// assert_eq!(event.token(), Token(1));
+/// assert!(event.is_writable());
+/// assert_eq!(mock.elapsed(), Duration::from_secs(10));
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Mock {
+    shared: Arc<MockState>,
+}
+
+/// A registration of an event source with a mock [`Poll`].
+///
+/// See [`Mock::registrations`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Registration {
+    token: Token,
+    interests: Interest,
+}
+
+impl Registration {
+    /// Returns the token of the registration.
+    pub const fn token(&self) -> Token {
+        self.token
+    }
+
+    /// Returns the interests of the registration.
+    pub const fn interests(&self) -> Interest {
+        self.interests
+    }
+}
+
+/// A call made to the [`Registry`] of a mock [`Poll`].
+///
+/// See [`Mock::calls`].
+///
+/// [`Registry`]: crate::Registry
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Call {
+    /// An event source was registered, see [`Registry::register`].
+    ///
+    /// [`Registry::register`]: crate::Registry::register
+    Register(Token, Interest),
+    /// An event source was reregistered, see [`Registry::reregister`].
+    ///
+    /// [`Registry::reregister`]: crate::Registry::reregister
+    Reregister(Token, Interest),
+    /// An event source was deregistered, see [`Registry::deregister`].
+    ///
+    /// [`Registry::deregister`]: crate::Registry::deregister
+    Deregister(Token),
+}
+
+impl Mock {
+    /// Create a new mock `Poll` and the handle to control it.
+    pub fn new() -> io::Result<(Poll, Mock)> {
+        let shared = Arc::new(MockState {
+            inner: Mutex::new(State {
+                elapsed: Duration::ZERO,
+                ready: VecDeque::new(),
+                scheduled: Vec::new(),
+                registrations: HashMap::new(),
+                calls: Vec::new(),
+            }),
+        });
+        let poll = Poll::new_mock(Arc::clone(&shared))?;
+        Ok((poll, Mock { shared }))
+    }
+
+    /// Inject an event with `readiness` for `token`, returned by the next call
+    /// to [`Poll::poll`].
+    ///
+    /// The readiness is mapped to the closest event the OS selector can
+    /// return. This means the event may contain more readiness than requested,
+    /// e.g. most selectors consider an event that is read closed to also be
+    /// readable.
+    pub fn inject(&self, token: Token, readiness: Readiness) {
+        self.shared.lock().ready.push_back((token, readiness));
+    }
+
+    /// Inject an event with `readiness` for `token` once `delay` has elapsed
+    /// on the virtual clock.
+    ///
+    /// See [`Mock::inject`].
+    pub fn inject_after(&self, delay: Duration, token: Token, readiness: Readiness) {
+        let mut state = self.shared.lock();
+        let deadline = state.elapsed + delay;
+        // Keep the events sorted by deadline, keeping the order in which they
+        // were injected for events with the same deadline.
+        let index = state.scheduled.partition_point(|(d, _, _)| *d <= deadline);
+        state.scheduled.insert(index, (deadline, token, readiness));
+    }
+
+    /// Move the virtual clock forward by `duration`.
+    ///
+    /// Any events scheduled using [`Mock::inject_after`] in that period will
+    /// be returned by the next call to [`Poll::poll`].
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.shared.lock();
+        let elapsed = state.elapsed + duration;
+        state.advance_to(elapsed);
+    }
+
+    /// Returns the time elapsed on the virtual clock.
+    pub fn elapsed(&self) -> Duration {
+        self.shared.lock().elapsed
+    }
+
+    /// Returns all currently active registrations, ordered by token.
+    ///
+    /// # Notes
+    ///
+    /// Event sources that are dropped without being deregistered are still
+    /// returned.
+    pub fn registrations(&self) -> Vec<Registration> {
+        let mut registrations: Vec<Registration> =
+            self.shared.lock().registrations.values().copied().collect();
+        registrations.sort_by_key(|r| r.token);
+        registrations
+    }
+
+    /// Returns the interests the event source registered with `token` has.
+    pub fn interests(&self, token: Token) -> Option<Interest> {
+        self.shared
+            .lock()
+            .registrations
+            .values()
+            .find(|r| r.token == token)
+            .map(|r| r.interests)
+    }
+
+    /// Returns all the calls made to the `Registry`, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.shared.lock().calls.clone()
+    }
+
+    /// Returns all the calls made to the `Registry`, in order, and clears
+    /// them.
+    pub fn take_calls(&self) -> Vec<Call> {
+        std::mem::take(&mut self.shared.lock().calls)
+    }
+}
+
+impl fmt::Debug for Mock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mock").finish()
+    }
+}
+
+/// State shared between [`Mock`] and the mock [`Poll`].
+pub(crate) struct MockState {
+    inner: Mutex<State>,
+}
+
+struct State {
+    elapsed: Duration,
+    /// Events ready to be returned.
+    ready: VecDeque<(Token, Readiness)>,
+    /// Events scheduled for later, sorted by deadline.
+    scheduled: Vec<(Duration, Token, Readiness)>,
+    registrations: HashMap<RawFd, Registration>,
+    calls: Vec<Call>,
+}
+
+impl State {
+    /// Move the clock to `elapsed`, marking all scheduled events up to that
+    /// point as ready.
+    fn advance_to(&mut self, elapsed: Duration) {
+        self.elapsed = elapsed;
+        let n = self.scheduled.partition_point(|(d, _, _)| *d <= elapsed);
+        self.ready.extend(
+            self.scheduled
+                .drain(..n)
+                .map(|(_, token, readiness)| (token, readiness)),
+        );
+    }
+}
+
+impl MockState {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    pub(crate) fn select(
+        &self,
+        events: &mut sys::Events,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        events.clear();
+        let mut state = self.lock();
+
+        if state.ready.is_empty() {
+            let next = state.scheduled.first().map(|(d, _, _)| *d);
+            let elapsed = match (timeout, next) {
+                (Some(timeout), Some(next)) => (state.elapsed + timeout).min(next),
+                (Some(timeout), None) => state.elapsed + timeout,
+                (None, Some(next)) => next,
+                (None, None) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "mock `Poll` would block forever, no events are ready or scheduled",
+                    ))
+                }
+            };
+            state.advance_to(elapsed);
+        }
+
+        let capacity = events.capacity();
+        while events.len() < capacity {
+            match state.ready.pop_front() {
+                Some((token, readiness)) => sys::event::from_readiness(token, readiness, events),
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    // Registering is only possible with the `net` or `os-ext` feature.
+    #[cfg_attr(not(any(feature = "net", feature = "os-ext")), allow(dead_code))]
+    pub(crate) fn register(&self, fd: RawFd, token: Token, interests: Interest) {
+        let mut state = self.lock();
+        let registration = Registration { token, interests };
+        state.registrations.insert(fd, registration);
+        state.calls.push(Call::Register(token, interests));
+    }
+
+    #[cfg_attr(not(any(feature = "net", feature = "os-ext")), allow(dead_code))]
+    pub(crate) fn reregister(&self, fd: RawFd, token: Token, interests: Interest) {
+        let mut state = self.lock();
+        let registration = Registration { token, interests };
+        state.registrations.insert(fd, registration);
+        state.calls.push(Call::Reregister(token, interests));
+    }
+
+    #[cfg_attr(not(any(feature = "net", feature = "os-ext")), allow(dead_code))]
+    pub(crate) fn deregister(&self, fd: RawFd) {
+        let mut state = self.lock();
+        if let Some(registration) = state.registrations.remove(&fd) {
+            state.calls.push(Call::Deregister(registration.token));
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Utilities for testing code using Mio.
+//!
+//! [`expect_events`] and [`expect_no_events`] can be used to check the events
+//! returned by [`Poll::poll`], with [`ExpectEvent`] describing an expected
+//! event. The [`checked_write!`] macro checks that an entire buffer is written.
+//!
+//! The [`Mock`] type (Unix only) provides a mock [`Poll`] that returns
+//! injected events, which allows for deterministic testing of event driven
+//! code.
+//!
+//! The [`Faults`] type (Unix only) provides a [`Poll`] that injects faults,
+//! such as spurious wakeups or errors, into operations on the OS selector.
+//!
+//! [`Poll`]: crate::Poll
+//! [`Poll::poll`]: crate::Poll::poll
+//! [`checked_write!`]: crate::checked_write
+
+mod expect;
+pub use self::expect::{expect_events, expect_no_events, ExpectEvent};
+
+#[cfg(unix)]
+mod faults;
+#[cfg(unix)]
+pub use self::faults::{Fault, Faults, Operation};
+#[cfg(unix)]
+pub(crate) use self::faults::{FaultState, Selector};
+
+#[cfg(unix)]
+mod mock;
+#[cfg(unix)]
+pub(crate) use self::mock::MockState;
+#[cfg(unix)]
+pub use self::mock::{Call, Mock, Registration};
+
+mod readiness;
+pub use self::readiness::Readiness;
+
+#[cfg(not(target_os = "wasi"))]
+mod temp;
+#[cfg(not(target_os = "wasi"))]
+pub use self::temp::temp_path;
+
+/// A checked `write`, `send` or `send_to` call that ensures the entire buffer
+/// is written.
+///
+/// # Panics
+///
+/// This panics if the call returns an error or if not the entire buffer is
+/// written.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::io::Write;
+///
+/// use mio::checked_write;
+///
+/// let mut buf = Vec::new();
+/// checked_write!(buf.write(b"Hello world"));
+///
+/// // Also works with arguments after the buffer, e.g. the address for
+/// // `send_to`, and fields.
+/// struct Conn { buf: Vec<u8> }
+/// let mut conn = Conn { buf };
+/// checked_write!(conn.buf.write(b"!"));
+/// assert_eq!(conn.buf, b"Hello world!");
+/// #     Ok(())
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+macro_rules! checked_write {
+    ($( $socket: ident ).+ ( $data: expr $(, $arg: expr)* $(,)? )) => {{
+        let data = $data;
+        let n = $( $socket ).+(data $(, $arg)*).expect("unable to write");
+        assert_eq!(n, data.len(), "short write: wrote {} of {} bytes", n, data.len());
+    }};
+}
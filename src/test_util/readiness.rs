@@ -0,0 +1,219 @@
+use std::{fmt, ops};
+
+use crate::event::Event;
+use crate::Interest;
+
+/// Readiness of an [`Event`].
+///
+/// Used to inject events into a mock `Poll`, see `Mock`, and to describe
+/// expected events, see [`ExpectEvent`].
+///
+/// [`ExpectEvent`]: crate::test_util::ExpectEvent
+///
+/// [`Event`]: crate::event::Event
+///
+/// # Examples
+///
+/// ```
+/// use mio::test_util::Readiness;
+///
+/// let readiness = Readiness::READABLE | Readiness::READ_CLOSED;
+///
+/// assert!(readiness.is_readable());
+/// assert!(readiness.is_read_closed());
+/// assert!(!readiness.is_writable());
+/// ```
+#[derive(Copy, PartialEq, Eq, Clone)]
+pub struct Readiness(u8);
+
+// These must be unique.
+const READABLE: u8 = 0b0000_0001;
+const WRITABLE: u8 = 0b0000_0010;
+const AIO: u8 = 0b0000_0100;
+const LIO: u8 = 0b0000_1000;
+const ERROR: u8 = 0b0001_0000;
+const READ_CLOSED: u8 = 0b0010_0000;
+const WRITE_CLOSED: u8 = 0b0100_0000;
+const PRIORITY: u8 = 0b1000_0000;
+
+impl Readiness {
+    /// Readable readiness, see [`Event::is_readable`].
+    ///
+    /// [`Event::is_readable`]: crate::event::Event::is_readable
+    pub const READABLE: Readiness = Readiness(READABLE);
+
+    /// Writable readiness, see [`Event::is_writable`].
+    ///
+    /// [`Event::is_writable`]: crate::event::Event::is_writable
+    pub const WRITABLE: Readiness = Readiness(WRITABLE);
+
+    /// AIO completion, see [`Event::is_aio`].
+    ///
+    /// [`Event::is_aio`]: crate::event::Event::is_aio
+    pub const AIO: Readiness = Readiness(AIO);
+
+    /// LIO completion, see [`Event::is_lio`].
+    ///
+    /// [`Event::is_lio`]: crate::event::Event::is_lio
+    pub const LIO: Readiness = Readiness(LIO);
+
+    /// Error readiness, see [`Event::is_error`].
+    ///
+    /// [`Event::is_error`]: crate::event::Event::is_error
+    pub const ERROR: Readiness = Readiness(ERROR);
+
+    /// Read closed readiness, see [`Event::is_read_closed`].
+    ///
+    /// [`Event::is_read_closed`]: crate::event::Event::is_read_closed
+    pub const READ_CLOSED: Readiness = Readiness(READ_CLOSED);
+
+    /// Write closed readiness, see [`Event::is_write_closed`].
+    ///
+    /// [`Event::is_write_closed`]: crate::event::Event::is_write_closed
+    pub const WRITE_CLOSED: Readiness = Readiness(WRITE_CLOSED);
+
+    /// Priority readiness, see [`Event::is_priority`].
+    ///
+    /// [`Event::is_priority`]: crate::event::Event::is_priority
+    pub const PRIORITY: Readiness = Readiness(PRIORITY);
+
+    /// Add together two `Readiness`.
+    ///
+    /// This does the same thing as the `BitOr` implementation, but is a
+    /// constant function.
+    #[allow(clippy::should_implement_trait)]
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub const fn add(self, other: Readiness) -> Readiness {
+        Readiness(self.0 | other.0)
+    }
+
+    /// Returns true if the value includes readable readiness.
+    #[must_use]
+    pub const fn is_readable(self) -> bool {
+        (self.0 & READABLE) != 0
+    }
+
+    /// Returns true if the value includes writable readiness.
+    #[must_use]
+    pub const fn is_writable(self) -> bool {
+        (self.0 & WRITABLE) != 0
+    }
+
+    /// Returns true if the value includes AIO readiness.
+    #[must_use]
+    pub const fn is_aio(self) -> bool {
+        (self.0 & AIO) != 0
+    }
+
+    /// Returns true if the value includes LIO readiness.
+    #[must_use]
+    pub const fn is_lio(self) -> bool {
+        (self.0 & LIO) != 0
+    }
+
+    /// Returns true if the value includes error readiness.
+    #[must_use]
+    pub const fn is_error(self) -> bool {
+        (self.0 & ERROR) != 0
+    }
+
+    /// Returns true if the value includes read closed readiness.
+    #[must_use]
+    pub const fn is_read_closed(self) -> bool {
+        (self.0 & READ_CLOSED) != 0
+    }
+
+    /// Returns true if the value includes write closed readiness.
+    #[must_use]
+    pub const fn is_write_closed(self) -> bool {
+        (self.0 & WRITE_CLOSED) != 0
+    }
+
+    /// Returns true if the value includes priority readiness.
+    #[must_use]
+    pub const fn is_priority(self) -> bool {
+        (self.0 & PRIORITY) != 0
+    }
+
+    /// Returns true if `event` has (at least) all the readiness in this value.
+    pub fn matches(self, event: &Event) -> bool {
+        // If we expect a readiness then also match on the event.
+        // In maths terms that is p -> q, which is the same as !p || q.
+        (!self.is_readable() || event.is_readable())
+            && (!self.is_writable() || event.is_writable())
+            && (!self.is_aio() || event.is_aio())
+            && (!self.is_lio() || event.is_lio())
+            && (!self.is_error() || event.is_error())
+            && (!self.is_read_closed() || event.is_read_closed())
+            && (!self.is_write_closed() || event.is_write_closed())
+            && (!self.is_priority() || event.is_priority())
+    }
+}
+
+impl ops::BitOr for Readiness {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        self.add(other)
+    }
+}
+
+impl ops::BitOrAssign for Readiness {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 = (*self | other).0;
+    }
+}
+
+impl From<Interest> for Readiness {
+    fn from(interests: Interest) -> Readiness {
+        let mut readiness = Readiness(0);
+        if interests.is_readable() {
+            readiness.0 |= READABLE;
+        }
+        if interests.is_writable() {
+            readiness.0 |= WRITABLE;
+        }
+        if interests.is_aio() {
+            readiness.0 |= AIO;
+        }
+        if interests.is_lio() {
+            readiness.0 |= LIO;
+        }
+        if interests.is_priority() {
+            readiness.0 |= PRIORITY;
+        }
+        readiness
+    }
+}
+
+impl fmt::Debug for Readiness {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const NAMES: [(u8, &str); 8] = [
+            (READABLE, "READABLE"),
+            (WRITABLE, "WRITABLE"),
+            (AIO, "AIO"),
+            (LIO, "LIO"),
+            (ERROR, "ERROR"),
+            (READ_CLOSED, "READ_CLOSED"),
+            (WRITE_CLOSED, "WRITE_CLOSED"),
+            (PRIORITY, "PRIORITY"),
+        ];
+
+        let mut one = false;
+        for (flag, name) in NAMES {
+            if self.0 & flag != 0 {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "{name}")?;
+                one = true
+            }
+        }
+        if !one {
+            write!(fmt, "(empty)")?;
+        }
+        Ok(())
+    }
+}
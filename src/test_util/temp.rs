@@ -0,0 +1,25 @@
+use std::env;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Returns a unique path in the temporary directory, with `name` as suffix.
+///
+/// The path is unique within the process and between processes running at the
+/// same time. The file is not created. This is useful for e.g. binding Unix
+/// sockets.
+///
+/// # Notes
+///
+/// The path is not removed automatically.
+///
+/// Unix socket addresses have a limited length (around 100 bytes), so keep
+/// `name` short if the path is used for one.
+#[cfg(not(target_os = "wasi"))]
+pub fn temp_path(name: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let mut path = env::temp_dir();
+    path.push(format!("mio_{}_{n}_{name}", process::id()));
+    path
+}
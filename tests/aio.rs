@@ -13,7 +13,7 @@ use mio::event::Source;
 use mio::{Events, Interest, Poll, Registry, Token};
 
 mod util;
-use util::{expect_events, expect_no_events, init, temp_file, ExpectEvent};
+use util::{expect_events, expect_no_events, init, temp_path, ExpectEvent};
 
 const UDATA: Token = Token(0xdead_beef);
 
@@ -181,7 +181,7 @@ mod aio {
         let mut poll = Poll::new().unwrap();
         let mut events = Events::with_capacity(8);
 
-        let f = File::create(temp_file("aio::smoke")).unwrap();
+        let f = File::create(temp_path("aio::smoke")).unwrap();
         let mut aiocb = Aiocb::from_fd(f.as_raw_fd());
         poll.registry()
             .register(&mut aiocb, UDATA, Interest::AIO)
@@ -208,8 +208,8 @@ mod lio {
         let mut poll = Poll::new().unwrap();
         let mut events = Events::with_capacity(8);
 
-        let f0 = File::create(temp_file("lio::smoke0")).unwrap();
-        let f1 = File::create(temp_file("lio::smoke1")).unwrap();
+        let f0 = File::create(temp_path("lio::smoke0")).unwrap();
+        let f1 = File::create(temp_path("lio::smoke1")).unwrap();
         let aiocb0 = Aiocb::from_slice(f0.as_raw_fd(), data);
         let aiocb1 = Aiocb::from_slice(f1.as_raw_fd(), data);
         let mut liocb = Liocb::new(vec![aiocb0, aiocb1]);
@@ -0,0 +1,300 @@
+#![cfg(all(unix, feature = "test-util", feature = "net"))]
+
+use std::io;
+use std::time::Duration;
+
+use mio::event::Event;
+use mio::net::{HappyEyeballs, TcpListener, TcpStream, UdpSocket};
+use mio::test_util::{Fault, Faults, Operation};
+use mio::{Events, Interest, Token};
+
+mod util;
+use util::{any_local_address, init};
+
+const ID1: Token = Token(0);
+const ID2: Token = Token(1);
+
+const TIMEOUT: Option<Duration> = Some(Duration::from_millis(500));
+
+#[test]
+fn poll_interrupted() {
+    init();
+    let (mut poll, faults) = Faults::new(0).unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    let _stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    faults.script(Operation::Poll, [Some(Fault::Interrupted)]);
+    let err = poll.poll(&mut events, TIMEOUT).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    assert_eq!(err.raw_os_error(), Some(libc::EINTR));
+    assert!(events.is_empty());
+
+    // The event is not lost.
+    poll.poll(&mut events, TIMEOUT).unwrap();
+    let tokens: Vec<Token> = events.iter().map(Event::token).collect();
+    assert_eq!(tokens, [ID1]);
+    assert_eq!(faults.injected(), [(Operation::Poll, Fault::Interrupted)]);
+}
+
+#[test]
+fn poll_spurious_wakeup() {
+    init();
+    let (mut poll, faults) = Faults::new(0).unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    // Returns immediately, even without any events.
+    faults.script(Operation::Poll, [Some(Fault::SpuriousWakeup)]);
+    poll.poll(&mut events, None).unwrap();
+    assert!(events.is_empty());
+
+    let _stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.poll(&mut events, TIMEOUT).unwrap();
+    let tokens: Vec<Token> = events.iter().map(Event::token).collect();
+    assert_eq!(tokens, [ID1]);
+}
+
+#[test]
+fn poll_duplicate() {
+    init();
+    let (mut poll, faults) = Faults::new(0).unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    faults.script(Operation::Poll, [Some(Fault::Duplicate)]);
+    poll.poll(&mut events, TIMEOUT).unwrap();
+    let tokens: Vec<Token> = events.iter().map(Event::token).collect();
+    assert_eq!(tokens, [ID1, ID1]);
+    assert_eq!(faults.injected(), [(Operation::Poll, Fault::Duplicate)]);
+
+    // Without events there is nothing to duplicate, so no fault is recorded.
+    poll.registry().deregister(&mut socket).unwrap();
+    faults.script(Operation::Poll, [Some(Fault::Duplicate)]);
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+    assert!(events.is_empty());
+    assert_eq!(faults.injected(), [(Operation::Poll, Fault::Duplicate)]);
+}
+
+#[test]
+fn poll_reorder() {
+    init();
+    let (mut poll, faults) = Faults::new(1).unwrap();
+    let mut events = Events::with_capacity(16);
+
+    let mut sockets: Vec<UdpSocket> = (0..8)
+        .map(|_| UdpSocket::bind(any_local_address()).unwrap())
+        .collect();
+    for (i, socket) in sockets.iter_mut().enumerate() {
+        poll.registry()
+            .register(socket, Token(i), Interest::WRITABLE)
+            .unwrap();
+    }
+
+    faults.script(Operation::Poll, [Some(Fault::Reorder)]);
+    let mut tokens = Vec::new();
+    while tokens.len() < sockets.len() {
+        poll.poll(&mut events, TIMEOUT).unwrap();
+        assert!(!events.is_empty(), "missing events, got: {tokens:?}");
+        tokens.extend(events.iter().map(Event::token));
+    }
+    // All events are still returned, only once.
+    tokens.sort();
+    let expected: Vec<Token> = (0..sockets.len()).map(Token).collect();
+    assert_eq!(tokens, expected);
+}
+
+#[test]
+fn register_errors() {
+    init();
+    let (mut poll, faults) = Faults::new(0).unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    faults.script(
+        Operation::Register,
+        [Some(Fault::OutOfMemory), Some(Fault::NoSpace)],
+    );
+    let err = poll
+        .registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOMEM));
+    let err = poll
+        .registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOSPC));
+
+    // Once the faults are exhausted registering works as normal.
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    let _stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.poll(&mut events, TIMEOUT).unwrap();
+    let tokens: Vec<Token> = events.iter().map(Event::token).collect();
+    assert_eq!(tokens, [ID1]);
+}
+
+#[test]
+fn reregister_error() {
+    init();
+    let (mut poll, faults) = Faults::new(0).unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::READABLE)
+        .unwrap();
+
+    faults.script(Operation::Reregister, [Some(Fault::OutOfMemory)]);
+    let err = poll
+        .registry()
+        .reregister(&mut socket, ID2, Interest::WRITABLE)
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOMEM));
+
+    poll.registry()
+        .reregister(&mut socket, ID2, Interest::WRITABLE)
+        .unwrap();
+    poll.poll(&mut events, TIMEOUT).unwrap();
+    let tokens: Vec<Token> = events.iter().map(Event::token).collect();
+    assert_eq!(tokens, [ID2]);
+}
+
+#[test]
+fn deregister_error() {
+    init();
+    let (mut poll, faults) = Faults::new(0).unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    faults.script(Operation::Deregister, [Some(Fault::NotFound)]);
+    let err = poll.registry().deregister(&mut socket).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOENT));
+    assert_eq!(
+        faults.injected(),
+        [(Operation::Deregister, Fault::NotFound)]
+    );
+
+    // The fault is injected instead of calling the OS selector, so the socket
+    // is still registered.
+    poll.poll(&mut events, TIMEOUT).unwrap();
+    let tokens: Vec<Token> = events.iter().map(Event::token).collect();
+    assert_eq!(tokens, [ID1]);
+}
+
+#[test]
+fn happy_eyeballs_deregister_error() {
+    init();
+    let (mut poll, faults) = Faults::new(0).unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    let mut connect = HappyEyeballs::new([address], ID1);
+    connect.start(poll.registry()).unwrap();
+
+    // The connected stream is returned even if deregistering it fails.
+    faults.script(Operation::Deregister, [Some(Fault::NotFound)]);
+    let stream = loop {
+        poll.poll(&mut events, TIMEOUT).unwrap();
+        assert!(!events.is_empty(), "connect timed out");
+        let stream = events
+            .iter()
+            .find_map(|event| connect.handle_event(poll.registry(), event).unwrap());
+        if let Some(stream) = stream {
+            break stream;
+        }
+    };
+    assert_eq!(stream.peer_addr().unwrap(), address);
+    assert_eq!(
+        faults.injected(),
+        [(Operation::Deregister, Fault::NotFound)]
+    );
+}
+
+#[test]
+fn script_none_skips_call() {
+    init();
+    let (mut poll, faults) = Faults::new(0).unwrap();
+    let mut events = Events::with_capacity(8);
+
+    faults.script(Operation::Poll, [None, Some(Fault::Interrupted)]);
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+    let err = poll.poll(&mut events, Some(Duration::ZERO)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+}
+
+#[test]
+fn probability() {
+    init();
+    let (mut poll, faults) = Faults::new(0).unwrap();
+    let mut events = Events::with_capacity(8);
+
+    faults.set_probability(Operation::Poll, Fault::Interrupted, 1.0);
+    for _ in 0..10 {
+        let err = poll.poll(&mut events, Some(Duration::ZERO)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    }
+
+    // Scripted faults take precedence.
+    faults.script(Operation::Poll, [None]);
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+
+    faults.set_probability(Operation::Poll, Fault::Interrupted, 0.0);
+    for _ in 0..10 {
+        poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+    }
+
+    faults.set_probability(Operation::Poll, Fault::Interrupted, 1.0);
+    faults.clear();
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+}
+
+#[test]
+fn probability_same_seed() {
+    init();
+
+    fn run(seed: u64) -> Vec<(Operation, Fault)> {
+        let (mut poll, faults) = Faults::new(seed).unwrap();
+        let mut events = Events::with_capacity(8);
+        faults.set_probability(Operation::Poll, Fault::Interrupted, 0.3);
+        faults.set_probability(Operation::Poll, Fault::SpuriousWakeup, 0.3);
+        for _ in 0..100 {
+            let _ = poll.poll(&mut events, Some(Duration::ZERO));
+        }
+        faults.injected()
+    }
+
+    let injected = run(123);
+    assert!(!injected.is_empty());
+    assert!(injected.len() < 100);
+    assert!(injected.contains(&(Operation::Poll, Fault::Interrupted)));
+    assert!(injected.contains(&(Operation::Poll, Fault::SpuriousWakeup)));
+    assert_eq!(injected, run(123));
+}
+
+#[test]
+#[should_panic = "fault NoSpace can't be injected into Poll"]
+fn invalid_fault() {
+    let (_poll, faults) = Faults::new(0).unwrap();
+    faults.script(Operation::Poll, [Some(Fault::NoSpace)]);
+}
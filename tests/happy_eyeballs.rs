@@ -0,0 +1,194 @@
+#![cfg(all(unix, feature = "os-poll", feature = "net"))]
+
+use std::io;
+use std::net::{self, SocketAddr};
+use std::time::{Duration, Instant};
+
+use mio::net::{HappyEyeballs, TcpListener, TcpSocket, TcpStream};
+use mio::{Events, Poll, Token};
+
+#[macro_use]
+mod util;
+use util::{any_local_address, any_local_ipv6_address, assert_would_block, init_with_poll};
+
+const TOKEN: Token = Token(10);
+
+#[test]
+fn connect_first_address() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener_v6 = net::TcpListener::bind(any_local_ipv6_address()).unwrap();
+    let listener_v4 = net::TcpListener::bind(any_local_address()).unwrap();
+    let address_v6 = listener_v6.local_addr().unwrap();
+    let address_v4 = listener_v4.local_addr().unwrap();
+
+    let mut connect = HappyEyeballs::new(vec![address_v6, address_v4], TOKEN);
+    connect.start(poll.registry()).unwrap();
+    assert!(connect.owns(TOKEN));
+    assert!(!connect.owns(Token(TOKEN.0 + 1)));
+
+    let stream = drive(&mut poll, &mut events, &mut connect).unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), address_v6);
+    listener_v6.accept().unwrap();
+
+    // The second attempt was never started.
+    listener_v4.set_nonblocking(true).unwrap();
+    assert_would_block(listener_v4.accept().map(|_| ()));
+}
+
+#[test]
+fn connect_after_failure() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    // Failed attempts start the next attempt without waiting for the delay.
+    let mut connect = HappyEyeballs::new(vec![closed_address(true), address], TOKEN)
+        .attempt_delay(Duration::from_secs(10));
+    connect.start(poll.registry()).unwrap();
+
+    let start = Instant::now();
+    let stream = drive(&mut poll, &mut events, &mut connect).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(stream.peer_addr().unwrap(), address);
+    listener.accept().unwrap();
+}
+
+#[test]
+fn connect_after_delay() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (_pending_listener, _filler, pending) = pending_address(any_local_address());
+    let listener = net::TcpListener::bind(any_local_ipv6_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let delay = Duration::from_millis(50);
+    let mut connect = HappyEyeballs::new(vec![pending, address], TOKEN).attempt_delay(delay);
+    assert_eq!(connect.timeout(), None);
+    connect.start(poll.registry()).unwrap();
+    assert!(connect.timeout().unwrap() <= delay);
+
+    let start = Instant::now();
+    let stream = drive(&mut poll, &mut events, &mut connect).unwrap();
+    assert!(start.elapsed() >= delay - Duration::from_millis(5));
+    assert_eq!(stream.peer_addr().unwrap(), address);
+    assert!(connect.owns(Token(TOKEN.0 + 1)));
+    assert_eq!(connect.timeout(), None);
+    listener.accept().unwrap();
+}
+
+#[test]
+fn interleaves_address_families() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (_pending_listener, _filler, pending) = pending_address(any_local_ipv6_address());
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    // Attempted in the order: closed (IPv6), listener (IPv4), pending (IPv6).
+    // If the pending address would be attempted second we'd have to wait for
+    // the delay.
+    let addresses = vec![closed_address(true), pending, address];
+    let mut connect = HappyEyeballs::new(addresses, TOKEN).attempt_delay(Duration::from_secs(10));
+    connect.start(poll.registry()).unwrap();
+
+    let start = Instant::now();
+    let stream = drive(&mut poll, &mut events, &mut connect).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(stream.peer_addr().unwrap(), address);
+    listener.accept().unwrap();
+}
+
+#[test]
+fn all_attempts_fail() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let addresses = vec![closed_address(false), closed_address(true)];
+    let mut connect = HappyEyeballs::new(addresses, TOKEN);
+    let err = match connect.start(poll.registry()) {
+        Ok(()) => drive(&mut poll, &mut events, &mut connect).unwrap_err(),
+        // Connect can fail immediately for all addresses.
+        Err(err) => err,
+    };
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+}
+
+#[test]
+fn no_addresses() {
+    let (poll, _) = init_with_poll();
+
+    let mut connect = HappyEyeballs::new(Vec::new(), TOKEN);
+    let err = connect.start(poll.registry()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn token_overflow() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (_pending_listener, _filler, pending) = pending_address(any_local_address());
+    let listener = net::TcpListener::bind(any_local_ipv6_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    // The first attempt uses the maximum token, the second one overflows.
+    let mut connect =
+        HappyEyeballs::new(vec![pending, address], Token(usize::MAX)).attempt_delay(Duration::ZERO);
+    connect.start(poll.registry()).unwrap();
+    let err = drive(&mut poll, &mut events, &mut connect).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+/// Drive `connect` until it returns a stream or an error.
+fn drive(
+    poll: &mut Poll,
+    events: &mut Events,
+    connect: &mut HappyEyeballs,
+) -> io::Result<TcpStream> {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        assert!(Instant::now() < deadline, "connect timed out");
+        let timeout = connect
+            .timeout()
+            .map_or(Duration::from_secs(1), |t| t.min(Duration::from_secs(1)));
+        poll.poll(events, Some(timeout))?;
+        for event in events.iter() {
+            assert!(connect.owns(event.token()), "unexpected event: {event:?}");
+            if let Some(stream) = connect.handle_event(poll.registry(), event)? {
+                return Ok(stream);
+            }
+        }
+        connect.handle_timeout(poll.registry())?;
+    }
+}
+
+/// Returns an address on which nothing is listening.
+fn closed_address(ipv6: bool) -> SocketAddr {
+    let address = if ipv6 {
+        any_local_ipv6_address()
+    } else {
+        any_local_address()
+    };
+    // Port is no longer in use after dropping the listener.
+    net::TcpListener::bind(address)
+        .unwrap()
+        .local_addr()
+        .unwrap()
+}
+
+/// Returns an address to which connections stay pending, as the accept queue
+/// of the listener is full. The listener and the connection filling the queue
+/// must be kept alive.
+fn pending_address(address: SocketAddr) -> (TcpListener, net::TcpStream, SocketAddr) {
+    let socket = if address.is_ipv6() {
+        TcpSocket::new_v6()
+    } else {
+        TcpSocket::new_v4()
+    }
+    .unwrap();
+    socket.bind(address).unwrap();
+    let listener = socket.listen(0).unwrap();
+    let address = listener.local_addr().unwrap();
+    let filler = net::TcpStream::connect(address).unwrap();
+    (listener, filler, address)
+}
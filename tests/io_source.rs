@@ -0,0 +1,101 @@
+#![cfg(all(unix, feature = "os-poll", feature = "os-ext", feature = "net"))]
+
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+
+use mio::unix::IoSource;
+use mio::{Interest, Token};
+
+mod util;
+use util::{assert_would_block, expect_events, expect_no_events, init_with_poll, ExpectEvent};
+
+const ID1: Token = Token(0);
+const ID2: Token = Token(1);
+
+const DATA1: &[u8] = b"Hello world!";
+const DATA2: &[u8] = b"Hello mars!";
+
+fn pair() -> (IoSource<UnixStream>, IoSource<UnixStream>) {
+    let (s1, s2) = UnixStream::pair().unwrap();
+    s1.set_nonblocking(true).unwrap();
+    s2.set_nonblocking(true).unwrap();
+    (IoSource::new(s1), IoSource::new(s2))
+}
+
+#[test]
+fn io_source_events() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut s1, mut s2) = pair();
+    poll.registry()
+        .register(&mut s1, ID1, Interest::WRITABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut s2, ID2, Interest::READABLE)
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    let mut buf = [0; 20];
+    assert_would_block(s2.do_io(|mut s| s.read(&mut buf)));
+
+    let n = s1.do_io(|mut s| s.write(DATA1)).unwrap();
+    assert_eq!(n, DATA1.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+    let n = s2.do_io(|mut s| s.read(&mut buf)).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_would_block(s2.do_io(|mut s| s.read(&mut buf)));
+
+    // After hitting `WouldBlock` we must receive another event, also with
+    // the `poll(2)` based selector.
+    let n = s1.do_io(|mut s| s.write(DATA2)).unwrap();
+    assert_eq!(n, DATA2.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+    let n = s2.do_io(|mut s| s.read(&mut buf)).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+
+    poll.registry().deregister(&mut s1).unwrap();
+    poll.registry().deregister(&mut s2).unwrap();
+    let n = s1.do_io(|mut s| s.write(DATA1)).unwrap();
+    assert_eq!(n, DATA1.len());
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn io_source_registered_with_multiple_registries() {
+    let (poll1, _) = init_with_poll();
+    let (poll2, _) = init_with_poll();
+
+    let (mut s1, _s2) = pair();
+    poll1
+        .registry()
+        .register(&mut s1, ID1, Interest::READABLE)
+        .unwrap();
+    let err = poll2
+        .registry()
+        .register(&mut s1, ID1, Interest::READABLE)
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+}
+
+#[test]
+fn io_source_into_inner() {
+    let (s1, s2) = pair();
+    let s1 = s1.into_inner();
+    let s2 = s2.into_inner();
+    assert_eq!(s1.local_addr().unwrap().as_pathname(), None);
+    assert_eq!(s2.peer_addr().unwrap().as_pathname(), None);
+}
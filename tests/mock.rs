@@ -0,0 +1,253 @@
+#![cfg(all(unix, feature = "test-util", feature = "os-ext", feature = "net"))]
+
+use std::io;
+use std::os::fd::AsRawFd;
+use std::time::Duration;
+
+use mio::event::Event;
+use mio::net::{TcpListener, TcpStream};
+use mio::test_util::{Call, Mock, Readiness};
+use mio::unix::SourceFd;
+use mio::{Events, Interest, Token};
+
+mod util;
+use util::{any_local_address, init};
+
+const ID1: Token = Token(0);
+const ID2: Token = Token(1);
+const ID3: Token = Token(2);
+
+#[test]
+fn inject_events() {
+    init();
+    let (mut poll, mock) = Mock::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    mock.inject(ID1, Readiness::READABLE);
+    mock.inject(ID2, Readiness::WRITABLE);
+    poll.poll(&mut events, None).unwrap();
+
+    let got: Vec<&Event> = events.iter().collect();
+    assert_eq!(got.len(), 2);
+    assert_eq!(got[0].token(), ID1);
+    assert!(got[0].is_readable());
+    assert!(!got[0].is_writable());
+    assert_eq!(got[1].token(), ID2);
+    assert!(got[1].is_writable());
+    assert!(!got[1].is_readable());
+
+    // All events are consumed.
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+    assert!(events.is_empty());
+}
+
+#[test]
+fn inject_readiness() {
+    init();
+    let (mut poll, mock) = Mock::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    type Check = fn(&Event) -> bool;
+    let tests: [(Readiness, Check); 6] = [
+        (Readiness::READABLE, Event::is_readable),
+        (Readiness::WRITABLE, Event::is_writable),
+        (Readiness::ERROR, Event::is_error),
+        (Readiness::READ_CLOSED, Event::is_read_closed),
+        (Readiness::WRITE_CLOSED, Event::is_write_closed),
+        (Readiness::READ_CLOSED | Readiness::WRITE_CLOSED, |event| {
+            event.is_read_closed() && event.is_write_closed()
+        }),
+    ];
+    for (readiness, check) in tests {
+        mock.inject(ID1, readiness);
+        poll.poll(&mut events, None).unwrap();
+        assert!(events.iter().all(|event| event.token() == ID1));
+        assert!(
+            events.iter().any(check),
+            "missing readiness: {readiness:?}, got: {events:?}"
+        );
+    }
+}
+
+#[test]
+fn events_capacity() {
+    init();
+    let (mut poll, mock) = Mock::new().unwrap();
+    let mut events = Events::with_capacity(2);
+
+    mock.inject(ID1, Readiness::READABLE);
+    mock.inject(ID2, Readiness::READABLE);
+    mock.inject(ID3, Readiness::READABLE);
+
+    poll.poll(&mut events, None).unwrap();
+    let tokens: Vec<Token> = events.iter().map(Event::token).collect();
+    assert_eq!(tokens, [ID1, ID2]);
+
+    // Remaining events are returned by the next call.
+    poll.poll(&mut events, None).unwrap();
+    let tokens: Vec<Token> = events.iter().map(Event::token).collect();
+    assert_eq!(tokens, [ID3]);
+}
+
+#[test]
+fn virtual_clock() {
+    init();
+    let (mut poll, mock) = Mock::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    mock.inject_after(Duration::from_secs(20), ID2, Readiness::READABLE);
+    mock.inject_after(Duration::from_secs(10), ID1, Readiness::READABLE);
+    assert_eq!(mock.elapsed(), Duration::ZERO);
+
+    // Timeout passes before any event is ready.
+    poll.poll(&mut events, Some(Duration::from_secs(5)))
+        .unwrap();
+    assert!(events.is_empty());
+    assert_eq!(mock.elapsed(), Duration::from_secs(5));
+
+    // Clock only moves up to the first scheduled event.
+    poll.poll(&mut events, Some(Duration::from_secs(60)))
+        .unwrap();
+    let tokens: Vec<Token> = events.iter().map(Event::token).collect();
+    assert_eq!(tokens, [ID1]);
+    assert_eq!(mock.elapsed(), Duration::from_secs(10));
+
+    // Moving the clock manually makes the event ready without polling.
+    mock.advance(Duration::from_secs(15));
+    assert_eq!(mock.elapsed(), Duration::from_secs(25));
+    poll.poll(&mut events, Some(Duration::ZERO)).unwrap();
+    let tokens: Vec<Token> = events.iter().map(Event::token).collect();
+    assert_eq!(tokens, [ID2]);
+    assert_eq!(mock.elapsed(), Duration::from_secs(25));
+}
+
+#[test]
+fn ready_events_dont_advance_clock() {
+    init();
+    let (mut poll, mock) = Mock::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    mock.inject(ID1, Readiness::READABLE);
+    mock.inject_after(Duration::from_secs(1), ID2, Readiness::READABLE);
+
+    poll.poll(&mut events, Some(Duration::from_secs(10)))
+        .unwrap();
+    let tokens: Vec<Token> = events.iter().map(Event::token).collect();
+    assert_eq!(tokens, [ID1]);
+    assert_eq!(mock.elapsed(), Duration::ZERO);
+}
+
+#[test]
+fn would_block_forever() {
+    init();
+    let (mut poll, _mock) = Mock::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let err = poll.poll(&mut events, None).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+}
+
+#[test]
+fn record_registrations() {
+    init();
+    let (poll, mock) = Mock::new().unwrap();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    assert!(mock.registrations().is_empty());
+
+    poll.registry()
+        .register(&mut stream, ID2, Interest::WRITABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let registrations = mock.registrations();
+    assert_eq!(registrations.len(), 2);
+    assert_eq!(registrations[0].token(), ID1);
+    assert_eq!(registrations[0].interests(), Interest::READABLE);
+    assert_eq!(registrations[1].token(), ID2);
+    assert_eq!(registrations[1].interests(), Interest::WRITABLE);
+
+    poll.registry()
+        .reregister(&mut stream, ID3, Interest::READABLE | Interest::WRITABLE)
+        .unwrap();
+    assert_eq!(mock.interests(ID2), None);
+    assert_eq!(
+        mock.interests(ID3),
+        Some(Interest::READABLE | Interest::WRITABLE)
+    );
+
+    poll.registry().deregister(&mut listener).unwrap();
+    assert_eq!(mock.interests(ID1), None);
+    assert_eq!(mock.registrations().len(), 1);
+
+    assert_eq!(
+        mock.take_calls(),
+        [
+            Call::Register(ID2, Interest::WRITABLE),
+            Call::Register(ID1, Interest::READABLE),
+            Call::Reregister(ID3, Interest::READABLE | Interest::WRITABLE),
+            Call::Deregister(ID1),
+        ]
+    );
+    assert!(mock.calls().is_empty());
+}
+
+#[test]
+fn record_registrations_source_fd() {
+    init();
+    let (poll, mock) = Mock::new().unwrap();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let fd = listener.as_raw_fd();
+    poll.registry()
+        .register(&mut SourceFd(&fd), ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry().deregister(&mut SourceFd(&fd)).unwrap();
+
+    assert_eq!(
+        mock.calls(),
+        [
+            Call::Register(ID1, Interest::READABLE),
+            Call::Deregister(ID1),
+        ]
+    );
+}
+
+#[test]
+fn registry_try_clone() {
+    init();
+    let (poll, mock) = Mock::new().unwrap();
+    let registry = poll.registry().try_clone().unwrap();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    registry
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    assert_eq!(mock.calls(), [Call::Register(ID1, Interest::READABLE)]);
+}
+
+#[test]
+fn real_events_not_returned() {
+    init();
+    let (mut poll, mock) = Mock::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    let _stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    // The listener is ready, but only injected events are returned.
+    poll.poll(&mut events, Some(Duration::from_millis(100)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    mock.inject(ID1, Readiness::READABLE);
+    poll.poll(&mut events, None).unwrap();
+    let tokens: Vec<Token> = events.iter().map(Event::token).collect();
+    assert_eq!(tokens, [ID1]);
+}
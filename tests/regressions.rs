@@ -113,11 +113,11 @@ fn issue_1205() {
 #[cfg_attr(miri, ignore = "Miri doesn't support Unix domain sockets")]
 fn issue_1403() {
     use mio::net::UnixDatagram;
-    use util::temp_file;
+    use util::temp_path;
 
     init();
 
-    let path = temp_file("issue_1403");
+    let path = temp_path("issue_1403");
     let datagram1 = UnixDatagram::bind(&path).unwrap();
     let datagram2 = UnixDatagram::unbound().unwrap();
 
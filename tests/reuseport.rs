@@ -0,0 +1,231 @@
+#![cfg(all(
+    unix,
+    not(any(target_os = "solaris", target_os = "illumos")),
+    feature = "os-poll",
+    feature = "net"
+))]
+
+use std::io;
+use std::net::{self, SocketAddr};
+use std::time::Duration;
+
+use mio::net::{ReuseportGroup, TcpListener, UdpSocket};
+use mio::{Interest, Token};
+
+#[macro_use]
+mod util;
+use util::{any_local_address, assert_socket_non_blocking, assert_would_block, init_with_poll};
+
+const DATA: &[u8] = b"Hello world!";
+
+#[test]
+fn bind_tcp() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listeners = ReuseportGroup::new(4)
+        .backlog(128)
+        .bind_tcp(any_local_address())
+        .unwrap();
+    assert_eq!(listeners.len(), 4);
+    let address = listeners[0].local_addr().unwrap();
+    assert_ne!(address.port(), 0);
+    for (i, listener) in listeners.iter_mut().enumerate() {
+        assert_eq!(listener.local_addr().unwrap(), address);
+        assert_socket_non_blocking(listener);
+        poll.registry()
+            .register(listener, Token(i), Interest::READABLE)
+            .unwrap();
+    }
+
+    // Every connection is accepted by exactly one listener in the group.
+    let streams: Vec<net::TcpStream> = (0..16)
+        .map(|_| net::TcpStream::connect(address).unwrap())
+        .collect();
+    let mut accepted = 0;
+    while accepted < streams.len() {
+        poll.poll(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        assert!(!events.is_empty(), "only accepted {accepted} connections");
+        for event in events.iter() {
+            accepted += accept_all(&listeners[event.token().0]);
+        }
+    }
+    assert_eq!(accepted, streams.len());
+    for listener in &listeners {
+        assert_would_block(listener.accept());
+    }
+}
+
+#[test]
+fn bind_udp() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut sockets = ReuseportGroup::new(3)
+        .bind_udp(any_local_address())
+        .unwrap();
+    assert_eq!(sockets.len(), 3);
+    let address = sockets[0].local_addr().unwrap();
+    for (i, socket) in sockets.iter_mut().enumerate() {
+        assert_eq!(socket.local_addr().unwrap(), address);
+        assert_socket_non_blocking(socket);
+        poll.registry()
+            .register(socket, Token(i), Interest::READABLE)
+            .unwrap();
+    }
+
+    // Datagrams from the same source are always received by the same socket.
+    let sender = net::UdpSocket::bind(any_local_address()).unwrap();
+    for _ in 0..4 {
+        sender.send_to(DATA, address).unwrap();
+    }
+    let mut received = Vec::new();
+    while received.len() < 4 {
+        poll.poll(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        assert!(!events.is_empty(), "only received {received:?}");
+        for event in events.iter() {
+            let socket = &sockets[event.token().0];
+            let mut buf = [0; 20];
+            while let Ok((n, source)) = socket.recv_from(&mut buf) {
+                assert_eq!(&buf[..n], DATA);
+                assert_eq!(source, sender.local_addr().unwrap());
+                received.push(event.token());
+            }
+        }
+    }
+    assert!(received.iter().all(|token| *token == received[0]));
+}
+
+#[test]
+fn bind_to_specified_port() {
+    let address = net::TcpListener::bind(any_local_address())
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    // Port is no longer in use after dropping the listener above.
+    let listeners = ReuseportGroup::new(2).bind_tcp(address).unwrap();
+    for listener in &listeners {
+        assert_eq!(listener.local_addr().unwrap(), address);
+    }
+}
+
+#[test]
+fn address_in_use_without_reuseport() {
+    let listeners = ReuseportGroup::new(2)
+        .bind_tcp(any_local_address())
+        .unwrap();
+    let address = listeners[0].local_addr().unwrap();
+    let err = TcpListener::bind(address).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AddrInUse);
+}
+
+#[test]
+fn empty_group() {
+    let err = ReuseportGroup::new(0)
+        .bind_tcp(any_local_address())
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    let err = ReuseportGroup::new(0)
+        .bind_udp(any_local_address())
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn steer_udp_by_cpu() {
+    const SIZE: usize = 2;
+
+    let sockets = ReuseportGroup::new(SIZE)
+        .steer_by_cpu(true)
+        .bind_udp(any_local_address())
+        .unwrap();
+    let address = sockets[0].local_addr().unwrap();
+
+    // Over loopback the datagram is received on the CPU that sent it.
+    for cpu in allowed_cpus() {
+        if !pin_to_cpu(cpu) {
+            continue;
+        }
+        let sender = net::UdpSocket::bind(any_local_address()).unwrap();
+        sender.send_to(DATA, address).unwrap();
+
+        let expected = &sockets[cpu % SIZE];
+        let mut buf = [0; 20];
+        let (n, _) = recv_from_blocking(expected, &mut buf);
+        assert_eq!(&buf[..n], DATA, "cpu {cpu}");
+        for socket in &sockets {
+            assert_would_block(socket.recv_from(&mut buf));
+        }
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn steer_tcp_by_cpu() {
+    const SIZE: usize = 2;
+
+    let listeners = ReuseportGroup::new(SIZE)
+        .steer_by_cpu(true)
+        .bind_tcp(any_local_address())
+        .unwrap();
+    let address = listeners[0].local_addr().unwrap();
+
+    for cpu in allowed_cpus() {
+        if !pin_to_cpu(cpu) {
+            continue;
+        }
+        let stream = net::TcpStream::connect(address).unwrap();
+
+        // The blocking connect ensures the connection is in the accept queue.
+        let (accepted, peer_address) = listeners[cpu % SIZE].accept().unwrap();
+        assert_eq!(peer_address, stream.local_addr().unwrap(), "cpu {cpu}");
+        drop(accepted);
+        for listener in &listeners {
+            assert_would_block(listener.accept());
+        }
+    }
+}
+
+fn accept_all(listener: &TcpListener) -> usize {
+    let mut accepted = 0;
+    loop {
+        match listener.accept() {
+            Ok(_) => accepted += 1,
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => return accepted,
+            Err(err) => panic!("unexpected error accepting connection: {err}"),
+        }
+    }
+}
+
+fn recv_from_blocking(socket: &UdpSocket, buf: &mut [u8]) -> (usize, SocketAddr) {
+    for _ in 0..50 {
+        match socket.recv_from(buf) {
+            Ok(result) => return result,
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(10))
+            }
+            Err(err) => panic!("unexpected error receiving datagram: {err}"),
+        }
+    }
+    panic!("datagram not received");
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn allowed_cpus() -> Vec<usize> {
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::cpu_set_t>();
+    assert_eq!(unsafe { libc::sched_getaffinity(0, size, &mut set) }, 0);
+    (0..libc::CPU_SETSIZE as usize)
+        .filter(|cpu| unsafe { libc::CPU_ISSET(*cpu, &set) })
+        .collect()
+}
+
+/// Pins the current thread to `cpu`, returns false if that is not possible.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn pin_to_cpu(cpu: usize) -> bool {
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    unsafe { libc::CPU_SET(cpu, &mut set) };
+    let size = std::mem::size_of::<libc::cpu_set_t>();
+    unsafe { libc::sched_setaffinity(0, size, &set) == 0 }
+}
@@ -0,0 +1,153 @@
+#![cfg(all(unix, feature = "os-poll", feature = "os-ext", feature = "net"))]
+
+use std::io::{Read, Write};
+use std::net;
+use std::os::fd::{AsFd, AsRawFd, OwnedFd};
+
+use mio::net::{TcpListener, TcpStream};
+use mio::unix::{pipe, BorrowedSource, OwnedSource};
+use mio::{Interest, Token};
+
+#[macro_use]
+mod util;
+use util::{any_local_address, expect_events, expect_no_events, init_with_poll, ExpectEvent};
+
+const ID1: Token = Token(0);
+const ID2: Token = Token(1);
+
+const DATA: &[u8] = b"Hello world!";
+
+#[test]
+fn source_fd_from_borrowed_fd() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut sender, receiver) = pipe::new().unwrap();
+    poll.registry()
+        .register(
+            &mut BorrowedSource::new(receiver.as_fd()),
+            ID1,
+            Interest::READABLE,
+        )
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    checked_write!(sender.write(DATA));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    poll.registry()
+        .reregister(
+            &mut BorrowedSource::new(receiver.as_fd()),
+            ID2,
+            Interest::READABLE,
+        )
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+
+    poll.registry()
+        .deregister(&mut BorrowedSource::new(receiver.as_fd()))
+        .unwrap();
+    checked_write!(sender.write(DATA));
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+fn borrowed_source_same_fd() {
+    let (_, receiver) = pipe::new().unwrap();
+    let source = BorrowedSource::new(receiver.as_fd());
+    assert_eq!(source.as_raw_fd(), receiver.as_raw_fd());
+    let source = BorrowedSource::from(receiver.as_fd());
+    assert_eq!(source.as_raw_fd(), receiver.as_raw_fd());
+}
+
+#[test]
+fn owned_source_std_listener() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    listener.set_nonblocking(true).unwrap();
+    let address = listener.local_addr().unwrap();
+    let mut listener = OwnedSource::new(listener);
+    assert_eq!(listener.as_raw_fd(), listener.get_ref().as_fd().as_raw_fd());
+
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    let _stream = TcpStream::connect(address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    assert!(listener.get_ref().accept().is_ok());
+
+    poll.registry().deregister(&mut listener).unwrap();
+    let _stream = TcpStream::connect(address).unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // Convert into a Mio type.
+    let mut listener = TcpListener::from(listener);
+    poll.registry()
+        .register(&mut listener, ID2, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+    assert!(listener.accept().is_ok());
+}
+
+#[test]
+#[cfg(not(mio_unsupported_force_poll_poll))]
+fn owned_source_no_events_after_drop() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut sender, receiver) = pipe::new().unwrap();
+    let mut receiver = OwnedSource::new(OwnedFd::from(receiver));
+    poll.registry()
+        .register(&mut receiver, ID1, Interest::READABLE)
+        .unwrap();
+
+    // Dropping the source closes the fd, removing it from the selector.
+    drop(receiver);
+    // The read end is closed, so writing fails with `EPIPE`.
+    assert!(sender.write(DATA).is_err());
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+fn owned_source_into_pipe() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut sender, receiver) = pipe::new().unwrap();
+    let mut source = OwnedSource::new(OwnedFd::from(receiver));
+    poll.registry()
+        .register(&mut source, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry().deregister(&mut source).unwrap();
+
+    // Convert back into a Mio type.
+    let mut receiver = pipe::Receiver::from(source);
+    poll.registry()
+        .register(&mut receiver, ID2, Interest::READABLE)
+        .unwrap();
+
+    checked_write!(sender.write(DATA));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    expect_read!(receiver.read(&mut buf), DATA);
+}
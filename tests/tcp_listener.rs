@@ -1,6 +1,8 @@
 #![cfg(all(feature = "os-poll", feature = "net"))]
 
 use mio::net::TcpListener;
+#[cfg(unix)]
+use mio::net::{AcceptReserve, AcceptStop, Accepted};
 use mio::{Interest, Token};
 use std::io::{self, Read};
 use std::net::{self, SocketAddr};
@@ -15,6 +17,8 @@ use util::{
     assert_socket_non_blocking, assert_sync, assert_would_block, expect_events, expect_no_events,
     init, init_with_poll, ExpectEvent,
 };
+#[cfg(unix)]
+use util::{in_subprocess, limit_fds, set_fd_limit};
 
 const ID1: Token = Token(0);
 const ID2: Token = Token(1);
@@ -126,6 +130,173 @@ fn get_ttl_without_previous_set() {
     assert!(listener.take_error().unwrap().is_none());
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn defer_accept() {
+    use std::io::Write;
+    use std::time::Duration;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    assert_eq!(listener.defer_accept().unwrap(), None);
+    listener
+        .set_defer_accept(Some(Duration::from_secs(5)))
+        .unwrap();
+    assert!(listener.defer_accept().unwrap().is_some());
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+
+    // The connection can only be accepted once the client sent data.
+    let mut stream = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    expect_no_events(&mut poll, &mut events);
+    assert_would_block(listener.accept());
+    stream.write_all(b"Hello").unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let (_, peer_address) = listener.accept().unwrap();
+    assert_eq!(peer_address, stream.local_addr().unwrap());
+
+    listener.set_defer_accept(None).unwrap();
+    assert_eq!(listener.defer_accept().unwrap(), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn accept_many() {
+    init();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    let streams: Vec<net::TcpStream> = (0..5)
+        .map(|_| net::TcpStream::connect(address).unwrap())
+        .collect();
+
+    let mut accepted = Vec::new();
+    assert!(listener.accept_many(&mut accepted, 3).unwrap().is_limit());
+    assert_eq!(accepted.len(), 3);
+    assert!(listener
+        .accept_many(&mut accepted, 10)
+        .unwrap()
+        .is_would_block());
+    assert_eq!(accepted.len(), 5);
+    for ((stream, peer_address), client) in accepted.iter().zip(&streams) {
+        assert_eq!(*peer_address, client.local_addr().unwrap());
+        assert_socket_non_blocking(stream);
+        assert_socket_close_on_exec(stream);
+    }
+    assert!(listener.accept_many(&mut accepted, 0).unwrap().is_limit());
+}
+
+#[cfg(unix)]
+#[test]
+fn accept_many_unlimited() {
+    init();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    let _streams: Vec<net::TcpStream> = (0..2)
+        .map(|_| net::TcpStream::connect(address).unwrap())
+        .collect();
+
+    let mut accepted = Vec::new();
+    assert!(listener
+        .accept_many(&mut accepted, usize::MAX)
+        .unwrap()
+        .is_would_block());
+    assert_eq!(accepted.len(), 2);
+}
+
+#[cfg(unix)]
+#[test]
+fn accept_many_emfile() {
+    // Lowering the file descriptor limit affects the entire process.
+    if !in_subprocess("accept_many_emfile") {
+        return;
+    }
+    init();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    let _streams: Vec<net::TcpStream> = (0..2)
+        .map(|_| net::TcpStream::connect(address).unwrap())
+        .collect();
+
+    let mut accepted = Vec::new();
+    let limit = limit_fds();
+    let stop = listener.accept_many(&mut accepted, 10).unwrap();
+    set_fd_limit(limit);
+    match stop {
+        AcceptStop::Resources(err) => assert_eq!(err.raw_os_error(), Some(libc::EMFILE)),
+        stop => panic!("unexpected stop reason: {stop:?}"),
+    }
+    assert!(accepted.is_empty());
+
+    // The connections stay in the accept queue.
+    assert!(listener
+        .accept_many(&mut accepted, 10)
+        .unwrap()
+        .is_would_block());
+    assert_eq!(accepted.len(), 2);
+}
+
+#[cfg(unix)]
+#[test]
+fn accept_reserved() {
+    // Lowering the file descriptor limit affects the entire process.
+    if !in_subprocess("accept_reserved") {
+        return;
+    }
+    init();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    let mut reserve = AcceptReserve::new().unwrap();
+
+    let mut client = net::TcpStream::connect(address).unwrap();
+    let _stream = match listener.accept_reserved(&mut reserve).unwrap() {
+        Accepted::Stream(stream, peer_address) => {
+            assert_eq!(peer_address, client.local_addr().unwrap());
+            stream
+        }
+        accepted => panic!("unexpected result: {accepted:?}"),
+    };
+
+    // Out of file descriptors the connections are shed.
+    let mut clients: Vec<net::TcpStream> = (0..2)
+        .map(|_| net::TcpStream::connect(address).unwrap())
+        .collect();
+    let limit = limit_fds();
+    let mut shed = Vec::new();
+    let result = loop {
+        match listener.accept_reserved(&mut reserve) {
+            Ok(Accepted::Shed(peer_address)) => shed.push(peer_address),
+            result => break result,
+        }
+    };
+    set_fd_limit(limit);
+    assert_would_block(result);
+    assert!(reserve.is_reserved());
+    let client_addresses: Vec<SocketAddr> = clients
+        .iter()
+        .map(|client| client.local_addr().unwrap())
+        .collect();
+    assert_eq!(shed, client_addresses);
+    for client in &mut clients {
+        let mut buf = [0; 8];
+        assert_eq!(client.read(&mut buf).unwrap(), 0);
+    }
+
+    // The first connection is unaffected.
+    let mut buf = [0; 8];
+    client.set_nonblocking(true).unwrap();
+    assert_would_block(client.read(&mut buf));
+}
+
 #[cfg(unix)]
 #[test]
 fn raw_fd() {
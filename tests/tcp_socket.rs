@@ -0,0 +1,187 @@
+#![cfg(all(unix, feature = "os-poll", feature = "net"))]
+
+use std::io::{Read, Write};
+use std::net::{self, SocketAddr};
+
+use mio::net::TcpSocket;
+use mio::{Interest, Token};
+
+#[macro_use]
+mod util;
+use util::{
+    any_local_address, assert_socket_close_on_exec, assert_socket_non_blocking, expect_events,
+    init_with_poll, ExpectEvent,
+};
+
+const ID1: Token = Token(0);
+const ID2: Token = Token(1);
+
+const DATA: &[u8] = b"Hello world!";
+
+#[test]
+fn is_non_blocking_and_close_on_exec() {
+    let socket = TcpSocket::new_v4().unwrap();
+    assert_socket_non_blocking(&socket);
+    assert_socket_close_on_exec(&socket);
+
+    let socket = TcpSocket::new_v6().unwrap();
+    assert_socket_non_blocking(&socket);
+    assert_socket_close_on_exec(&socket);
+}
+
+#[test]
+fn listen_and_connect() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket = TcpSocket::new_for_addr(any_local_address()).unwrap();
+    socket.bind(any_local_address()).unwrap();
+    let mut listener = socket.listen(16).unwrap();
+    let address = listener.local_addr().unwrap();
+    assert_socket_non_blocking(&listener);
+
+    let socket = TcpSocket::new_for_addr(address).unwrap();
+    let mut stream = socket.connect(address).unwrap();
+    assert_socket_non_blocking(&stream);
+
+    poll.registry()
+        .register(&mut listener, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut stream, ID2, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![
+            ExpectEvent::new(ID1, Interest::READABLE),
+            ExpectEvent::new(ID2, Interest::WRITABLE),
+        ],
+    );
+
+    let (mut accepted, peer_address) = listener.accept().unwrap();
+    assert_eq!(peer_address, stream.local_addr().unwrap());
+    assert_eq!(stream.peer_addr().unwrap(), address);
+
+    poll.registry()
+        .register(&mut accepted, ID1, Interest::READABLE)
+        .unwrap();
+    checked_write!(stream.write(DATA));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; 20];
+    expect_read!(accepted.read(&mut buf), DATA);
+}
+
+#[test]
+fn bind_before_connect() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.bind(any_local_address()).unwrap();
+    let local_address = socket.local_addr().unwrap();
+    assert_ne!(local_address.port(), 0);
+
+    let stream = socket.connect(address).unwrap();
+    assert_eq!(stream.local_addr().unwrap(), local_address);
+    let (_, peer_address) = listener.accept().unwrap();
+    assert_eq!(peer_address, local_address);
+}
+
+#[test]
+fn local_addr_not_bound() {
+    let socket = TcpSocket::new_v4().unwrap();
+    let address = socket.local_addr().unwrap();
+    assert_eq!(address, "0.0.0.0:0".parse::<SocketAddr>().unwrap());
+}
+
+#[test]
+fn reuseaddr() {
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_reuseaddr(true).unwrap();
+    assert!(socket.reuseaddr().unwrap());
+    socket.set_reuseaddr(false).unwrap();
+    assert!(!socket.reuseaddr().unwrap());
+}
+
+#[test]
+#[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+fn reuseport() {
+    let socket1 = TcpSocket::new_v4().unwrap();
+    socket1.set_reuseport(true).unwrap();
+    assert!(socket1.reuseport().unwrap());
+    socket1.bind(any_local_address()).unwrap();
+    let address = socket1.local_addr().unwrap();
+    let _listener1 = socket1.listen(16).unwrap();
+
+    // Binding to the same address is allowed with `SO_REUSEPORT`.
+    let socket2 = TcpSocket::new_v4().unwrap();
+    socket2.set_reuseport(true).unwrap();
+    socket2.bind(address).unwrap();
+    let _listener2 = socket2.listen(16).unwrap();
+
+    // But not without.
+    let socket3 = TcpSocket::new_v4().unwrap();
+    assert!(socket3.bind(address).is_err());
+}
+
+#[test]
+fn only_v6() {
+    let socket = TcpSocket::new_v6().unwrap();
+    socket.set_only_v6(true).unwrap();
+    assert!(socket.only_v6().unwrap());
+    socket.bind("[::]:0".parse().unwrap()).unwrap();
+    let port = socket.local_addr().unwrap().port();
+    let _listener = socket.listen(16).unwrap();
+
+    // IPv4 connections are refused.
+    assert!(net::TcpStream::connect(("127.0.0.1", port)).is_err());
+}
+
+#[test]
+fn only_v6_ipv4_mapped() {
+    let socket = TcpSocket::new_v6().unwrap();
+    socket.set_only_v6(false).unwrap();
+    assert!(!socket.only_v6().unwrap());
+    socket.bind("[::]:0".parse().unwrap()).unwrap();
+    let port = socket.local_addr().unwrap().port();
+    let _listener = socket.listen(16).unwrap();
+
+    // IPv4 connections are accepted.
+    net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+}
+
+#[test]
+fn buffer_sizes() {
+    let socket = TcpSocket::new_v4().unwrap();
+
+    socket.set_send_buffer_size(16 * 1024).unwrap();
+    // The OS may change the value, e.g. Linux doubles it.
+    assert!(socket.send_buffer_size().unwrap() >= 16 * 1024);
+
+    socket.set_recv_buffer_size(16 * 1024).unwrap();
+    assert!(socket.recv_buffer_size().unwrap() >= 16 * 1024);
+}
+
+#[test]
+fn listen_backlog() {
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.bind(any_local_address()).unwrap();
+    let listener = socket.listen(u32::MAX).unwrap();
+    let address = listener.local_addr().unwrap();
+    net::TcpStream::connect(address).unwrap();
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn fastopen() {
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_fastopen(32).unwrap();
+    assert_eq!(socket.fastopen().unwrap(), 32);
+    socket.bind(any_local_address()).unwrap();
+    let listener = socket.listen(16).unwrap();
+    assert_eq!(listener.fastopen().unwrap(), 32);
+}
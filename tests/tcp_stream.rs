@@ -8,7 +8,9 @@ use std::sync::{mpsc::channel, Arc, Barrier};
 use std::thread;
 use std::time::Duration;
 
-use mio::net::TcpStream;
+#[cfg(unix)]
+use mio::net::MsgFlags;
+use mio::net::{ConnectStatus, TcpStream};
 use mio::{Interest, Token};
 
 #[macro_use]
@@ -294,6 +296,615 @@ fn get_nodelay_without_previous_set() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+fn connect_status_connected() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    loop {
+        poll.poll(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        assert!(!events.is_empty(), "not connected");
+        match stream.connect_status().unwrap() {
+            ConnectStatus::Pending => continue,
+            ConnectStatus::Connected => break,
+            ConnectStatus::Failed(err) => panic!("failed to connect: {err}"),
+        }
+    }
+    assert!(stream.connect_status().unwrap().is_connected());
+    assert_eq!(stream.peer_addr().unwrap(), listener.local_addr().unwrap());
+}
+
+#[test]
+#[cfg_attr(
+    target_os = "wasi",
+    ignore = "WASI does not yet support connection refused errors"
+)]
+fn connect_status_refused() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Not listening on the address.
+    let address = net::TcpListener::bind(any_local_address())
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let mut stream = match TcpStream::connect(address) {
+        Ok(stream) => stream,
+        // Some platforms return the error immediately.
+        Err(err) => {
+            assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+            return;
+        }
+    };
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    match stream.connect_status().unwrap() {
+        ConnectStatus::Failed(err) => assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused),
+        status => panic!("unexpected status: {status:?}"),
+    }
+    // The error is cleared, so the failure is reported only once.
+    if let Ok(ConnectStatus::Failed(err)) = stream.connect_status() {
+        panic!("failure reported twice: {err}");
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn connect_status_pending() {
+    use mio::net::TcpSocket;
+    use std::os::fd::OwnedFd;
+
+    // A stream that is not (yet) connected, e.g. after a spurious event.
+    let socket = TcpSocket::new_v4().unwrap();
+    let stream = TcpStream::from(OwnedFd::from(socket));
+    assert!(stream.connect_status().unwrap().is_pending());
+}
+
+#[cfg(unix)]
+#[test]
+fn set_get_keepalive() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    assert!(!stream.keepalive().unwrap());
+    stream.set_keepalive(true).unwrap();
+    assert!(stream.keepalive().unwrap());
+
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "illumos",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "solaris",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "watchos",
+    ))]
+    {
+        stream.set_keepalive_time(Duration::from_secs(30)).unwrap();
+        assert_eq!(stream.keepalive_time().unwrap(), Duration::from_secs(30));
+        stream
+            .set_keepalive_interval(Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(stream.keepalive_interval().unwrap(), Duration::from_secs(5));
+        // Sub-second durations are rounded up to whole seconds.
+        stream
+            .set_keepalive_interval(Duration::from_millis(500))
+            .unwrap();
+        assert_eq!(stream.keepalive_interval().unwrap(), Duration::from_secs(1));
+        stream.set_keepalive_retries(3).unwrap();
+        assert_eq!(stream.keepalive_retries().unwrap(), 3);
+    }
+
+    stream.set_keepalive(false).unwrap();
+    assert!(!stream.keepalive().unwrap());
+    assert!(stream.take_error().unwrap().is_none());
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn set_get_user_timeout() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    assert_eq!(stream.user_timeout().unwrap(), None);
+    stream
+        .set_user_timeout(Some(Duration::from_millis(1500)))
+        .unwrap();
+    assert_eq!(
+        stream.user_timeout().unwrap(),
+        Some(Duration::from_millis(1500))
+    );
+    stream.set_user_timeout(None).unwrap();
+    assert_eq!(stream.user_timeout().unwrap(), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn set_get_linger() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    assert_eq!(stream.linger().unwrap(), None);
+    stream.set_linger(Some(Duration::from_secs(5))).unwrap();
+    assert_eq!(stream.linger().unwrap(), Some(Duration::from_secs(5)));
+    stream.set_linger(Some(Duration::ZERO)).unwrap();
+    assert_eq!(stream.linger().unwrap(), Some(Duration::ZERO));
+    stream.set_linger(None).unwrap();
+    assert_eq!(stream.linger().unwrap(), None);
+}
+
+#[cfg(unix)]
+#[test]
+#[cfg_attr(target_os = "hurd", ignore = "POLLRDHUP isn't supported on GNU/Hurd")]
+#[cfg_attr(target_os = "solaris", ignore = "POLLRDHUP isn't supported on Solaris")]
+#[cfg_attr(target_os = "nto", ignore = "POLLRDHUP isn't supported on NTO")]
+#[cfg_attr(target_os = "cygwin", ignore = "POLLRDHUP isn't supported on Cygwin")]
+fn abort() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server_stream, _) = listener.accept().unwrap();
+    server_stream.set_nonblocking(true).unwrap();
+    let mut server_stream = TcpStream::from_std(server_stream);
+    poll.registry()
+        .register(&mut server_stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    let stream = TcpStream::from_std(stream);
+    stream.abort().unwrap();
+
+    // The peer sees the reset, rather than the end of the stream.
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(
+            ID1,
+            Readiness::READ_CLOSED | Readiness::ERROR,
+        )],
+    );
+    let mut buf = [0; 16];
+    let err = server_stream.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+}
+
+#[cfg(unix)]
+#[test]
+fn linger_zero_on_drop_resets() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut server_stream, _) = listener.accept().unwrap();
+
+    stream.set_linger(Some(Duration::ZERO)).unwrap();
+    drop(stream);
+
+    let mut buf = [0; 16];
+    let err = server_stream.read(&mut buf).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ECONNRESET));
+}
+
+#[cfg(unix)]
+#[test]
+fn recv_lowat() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server_stream, _) = listener.accept().unwrap();
+    server_stream.set_nonblocking(true).unwrap();
+    let mut server_stream = TcpStream::from_std(server_stream);
+
+    assert_eq!(server_stream.recv_lowat().unwrap(), 1);
+    server_stream.set_recv_lowat(DATA1_LEN as u32).unwrap();
+    assert_eq!(server_stream.recv_lowat().unwrap(), DATA1_LEN as u32);
+    poll.registry()
+        .register(&mut server_stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    // Not readable until at least `DATA1_LEN` bytes are available.
+    checked_write!(stream.write(&DATA1[..4]));
+    expect_no_events(&mut poll, &mut events);
+    checked_write!(stream.write(&DATA1[4..]));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; 20];
+    expect_read!(server_stream.read(&mut buf), DATA1);
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn notsent_lowat() {
+    use mio::net::TcpSocket;
+
+    const LOWAT: u32 = 16 * 1024;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    // Fills the peer's receive window and the unsent backlog, returning the
+    // number of bytes written.
+    fn fill(stream: &mut TcpStream) -> usize {
+        let data = [0; 4 * 1024];
+        let mut written = 0;
+        loop {
+            match stream.write(&data) {
+                Ok(n) => written += n,
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => return written,
+                Err(err) => panic!("unexpected error: {err}"),
+            }
+        }
+    }
+
+    let connect = || {
+        let socket = TcpSocket::new_for_addr(address).unwrap();
+        socket.set_send_buffer_size(1024 * 1024).unwrap();
+        let stream = socket.connect(address).unwrap();
+        (stream, listener.accept().unwrap().0)
+    };
+    let (mut stream1, _server_stream1) = connect();
+    let (mut stream2, mut server_stream2) = connect();
+    stream2.set_notsent_lowat(LOWAT).unwrap();
+    assert_eq!(stream2.notsent_lowat().unwrap(), LOWAT);
+    poll.registry()
+        .register(&mut stream2, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    // The unsent backlog is limited, while the send buffer is not full.
+    let written1 = fill(&mut stream1);
+    let written2 = fill(&mut stream2);
+    assert!(written2 < written1, "{written2} >= {written1}");
+    expect_no_events(&mut poll, &mut events);
+
+    // Once the backlog drops below the threshold the stream is writable.
+    let mut buf = vec![0; written2];
+    server_stream2.read_exact(&mut buf).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn set_get_congestion() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    assert!(!stream.congestion().unwrap().is_empty());
+    // Reno is always available and allowed.
+    stream.set_congestion("reno").unwrap();
+    assert_eq!(stream.congestion().unwrap(), "reno");
+
+    let err = stream.set_congestion("not-an-algorithm").unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOENT));
+    assert_eq!(stream.congestion().unwrap(), "reno");
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn cork() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server_stream, _) = listener.accept().unwrap();
+    server_stream.set_nonblocking(true).unwrap();
+    let mut server_stream = TcpStream::from_std(server_stream);
+    poll.registry()
+        .register(&mut server_stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    assert!(!stream.cork().unwrap());
+    stream.set_cork(true).unwrap();
+    assert!(stream.cork().unwrap());
+
+    // Partial segments are held back while corked.
+    checked_write!(stream.write(DATA1));
+    checked_write!(stream.write(DATA2));
+    expect_no_events(&mut poll, &mut events);
+
+    stream.set_cork(false).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; DATA1_LEN + DATA2_LEN];
+    server_stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf[..DATA1_LEN], DATA1);
+    assert_eq!(&buf[DATA1_LEN..], DATA2);
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn set_get_quickack() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let _server_stream = listener.accept().unwrap();
+
+    stream.set_quickack(true).unwrap();
+    assert!(stream.quickack().unwrap());
+    stream.set_quickack(false).unwrap();
+    assert!(!stream.quickack().unwrap());
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn connect_with_data() {
+    use mio::net::TcpListener;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    const ID3: Token = Token(2);
+
+    let mut listener = TcpListener::bind(any_local_address()).unwrap();
+    listener.set_fastopen(16).unwrap();
+    assert_eq!(listener.fastopen().unwrap(), 16);
+    let address = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&mut listener, ID2, Interest::READABLE)
+        .unwrap();
+
+    // Data is only sent in the SYN once the client has a cookie, which it gets
+    // in the first connection if Fast Open is enabled for both clients and
+    // servers. Cookies are cached by the kernel, so it could also be available
+    // from a previous test run.
+    let enabled = std::fs::read_to_string("/proc/sys/net/ipv4/tcp_fastopen")
+        .ok()
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .is_some_and(|value| value & 0x3 == 0x3);
+    for i in 0..2 {
+        let (mut stream, n) = TcpStream::connect_with_data(address, DATA1).unwrap();
+        if enabled && i == 1 {
+            assert_eq!(n, DATA1_LEN);
+        } else {
+            assert!(n == 0 || n == DATA1_LEN, "{n}");
+        }
+        assert_socket_non_blocking(&stream);
+        assert_socket_close_on_exec(&stream);
+
+        poll.registry()
+            .register(&mut stream, ID1, Interest::WRITABLE)
+            .unwrap();
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![
+                ExpectEvent::new(ID1, Interest::WRITABLE),
+                ExpectEvent::new(ID2, Interest::READABLE),
+            ],
+        );
+        if n < DATA1_LEN {
+            checked_write!(stream.write(&DATA1[n..]));
+        }
+
+        let (mut accepted, _) = listener.accept().unwrap();
+        assert_would_block(listener.accept());
+        poll.registry()
+            .register(&mut accepted, ID3, Interest::READABLE)
+            .unwrap();
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID3, Interest::READABLE)],
+        );
+        let mut buf = [0; 20];
+        expect_read!(accepted.read(&mut buf), DATA1);
+        poll.registry().deregister(&mut stream).unwrap();
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn tcp_info() {
+    use mio::net::TcpState;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut server_stream, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    checked_write!(stream.write(DATA1));
+    let mut buf = [0; DATA1_LEN];
+    server_stream.read_exact(&mut buf).unwrap();
+    server_stream.write_all(DATA2).unwrap();
+    poll.registry()
+        .reregister(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let info = stream.tcp_info().unwrap();
+    assert_eq!(info.state(), TcpState::Established, "{info:?}");
+    assert!(info.send_mss() > 0, "{info:?}");
+    assert!(info.send_cwnd() > 0, "{info:?}");
+    assert!(info.rto() > Duration::ZERO, "{info:?}");
+    assert_eq!(info.total_retrans(), 0, "{info:?}");
+    // All fields are supported by the kernel versions we test on.
+    // Acknowledged bytes include the SYN.
+    assert_eq!(info.bytes_acked(), Some(DATA1_LEN as u64 + 1), "{info:?}");
+    assert_eq!(info.bytes_received(), Some(DATA2_LEN as u64), "{info:?}");
+    assert_eq!(info.notsent_bytes(), Some(0), "{info:?}");
+    assert_eq!(info.bytes_sent(), Some(DATA1_LEN as u64), "{info:?}");
+    assert_eq!(info.bytes_retrans(), Some(0), "{info:?}");
+    assert!(info.delivery_rate().is_some(), "{info:?}");
+    assert!(info.send_window().unwrap() > 0, "{info:?}");
+
+    drop(server_stream);
+    drop(listener);
+    stream.shutdown(Shutdown::Write).unwrap();
+    let info = stream.tcp_info().unwrap();
+    assert_ne!(info.state(), TcpState::Established, "{info:?}");
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn sendfile() {
+    use std::fs::File;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut file = File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(util::temp_path("tcp_stream_sendfile"))
+        .unwrap();
+    file.write_all(DATA1).unwrap();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut server_stream, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    // Sends from the offset, up to the end of the file.
+    assert_eq!(stream.sendfile(&file, 6, 100).unwrap(), DATA1_LEN - 6);
+    assert_eq!(stream.sendfile(&file, 0, 5).unwrap(), 5);
+    assert_eq!(stream.sendfile(&file, DATA1_LEN as u64, 100).unwrap(), 0);
+    let mut buf = [0; DATA1_LEN - 1];
+    server_stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"world!Hello");
+
+    // Partial sends once the send buffer is full.
+    let data = vec![1; 8 * 1024 * 1024];
+    file.write_all(&data).unwrap();
+    let mut offset = 0;
+    let err = loop {
+        match stream.sendfile(&file, offset, data.len()) {
+            Ok(n) => {
+                assert!(n > 0);
+                offset += n as u64;
+            }
+            Err(err) => break err,
+        }
+    };
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+    assert!(offset > 0 && offset < (DATA1_LEN + data.len()) as u64);
+}
+
+#[cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    // Deregisters the socket after an error event.
+    not(mio_unsupported_force_poll_poll)
+))]
+#[test]
+fn send_zerocopy() {
+    use mio::net::ZerocopyCompletion;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut server_stream, _) = listener.accept().unwrap();
+    assert!(!stream.zerocopy().unwrap());
+    stream.set_zerocopy(true).unwrap();
+    assert!(stream.zerocopy().unwrap());
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+    poll.registry()
+        .reregister(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+
+    for _ in 0..3 {
+        assert_eq!(stream.send_zerocopy(DATA1).unwrap(), DATA1_LEN);
+    }
+    let mut buf = [0; 3 * DATA1_LEN];
+    server_stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf[..DATA1_LEN], DATA1);
+
+    // The completions are reported as error event.
+    let mut completions = Vec::new();
+    while completions
+        .iter()
+        .map(ZerocopyCompletion::count)
+        .sum::<u64>()
+        < 3
+    {
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Readiness::ERROR)],
+        );
+        for completion in stream.zerocopy_completions() {
+            completions.push(completion.unwrap());
+        }
+    }
+    let mut next = 0;
+    for completion in completions {
+        assert_eq!(completion.first(), next, "{completion:?}");
+        assert!(completion.contains(next), "{completion:?}");
+        // Loopback always copies.
+        assert!(completion.is_copied(), "{completion:?}");
+        next = completion.last() + 1;
+    }
+    assert_eq!(next, 3);
+    assert!(stream.zerocopy_completions().next().is_none());
+}
+
 #[cfg_attr(
     target_os = "wasi",
     ignore = "WASI does not yet support multithreading"
@@ -1097,3 +1708,167 @@ fn read_peek_would_block() {
         vec![ExpectEvent::new(ID1, Readiness::READABLE)],
     );
 }
+
+#[test]
+#[cfg(unix)]
+fn send_recv_with_flags() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE | Interest::WRITABLE)
+        .unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::WRITABLE)],
+    );
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    let flags = MsgFlags::MORE | MsgFlags::NOSIGNAL;
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    let flags = MsgFlags::EMPTY;
+    assert_eq!(stream.send_with_flags(DATA1, flags).unwrap(), DATA1_LEN);
+    assert_eq!(
+        stream.send_with_flags(DATA2, MsgFlags::EMPTY).unwrap(),
+        DATA2_LEN
+    );
+    let mut buf = [0; DATA1_LEN + DATA2_LEN];
+    peer.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf[..DATA1_LEN], DATA1);
+    assert_eq!(&buf[DATA1_LEN..], DATA2);
+
+    peer.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    expect_read!(stream.recv_with_flags(&mut buf, MsgFlags::PEEK), DATA1);
+    expect_read!(stream.recv_with_flags(&mut buf, MsgFlags::EMPTY), DATA1);
+    assert_would_block(stream.recv_with_flags(&mut buf, MsgFlags::EMPTY));
+}
+
+#[cfg_attr(miri, ignore = "Miri doesn't support `Interest::PRIORITY`")]
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn send_recv_oob() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut client, ID1, Interest::READABLE | Interest::PRIORITY)
+        .unwrap();
+    let (stream, _) = listener.accept().unwrap();
+    stream.set_nonblocking(true).unwrap();
+    let server = TcpStream::from_std(stream);
+
+    assert!(!client.oobinline().unwrap());
+    assert_eq!(
+        server.send_with_flags(DATA1, MsgFlags::EMPTY).unwrap(),
+        DATA1_LEN
+    );
+    assert_eq!(server.send_oob(b"!").unwrap(), 1);
+
+    wait_for_priority_event(&mut poll, &mut events);
+
+    // Reading stops at the urgent data mark.
+    let mut buf = [0; 20];
+    assert!(!client.at_mark().unwrap());
+    expect_read!(client.read(&mut buf), DATA1);
+    assert!(client.at_mark().unwrap());
+    assert_eq!(client.recv_oob(&mut buf).unwrap(), 1);
+    assert_eq!(buf[0], b'!');
+    // The urgent data can only be read once.
+    assert!(client.recv_oob(&mut buf).is_err());
+    assert_would_block(client.read(&mut buf));
+}
+
+#[cfg_attr(miri, ignore = "Miri doesn't support `Interest::PRIORITY`")]
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn send_recv_oobinline() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    client.set_oobinline(true).unwrap();
+    assert!(client.oobinline().unwrap());
+    poll.registry()
+        .register(&mut client, ID1, Interest::READABLE | Interest::PRIORITY)
+        .unwrap();
+    let (stream, _) = listener.accept().unwrap();
+    stream.set_nonblocking(true).unwrap();
+    let server = TcpStream::from_std(stream);
+
+    // Only the last byte is sent as urgent data.
+    assert_eq!(server.send_oob(DATA1).unwrap(), DATA1_LEN);
+
+    wait_for_priority_event(&mut poll, &mut events);
+
+    // With `SO_OOBINLINE` the urgent data is read as normal data.
+    let mut buf = [0; 20];
+    expect_read!(client.read(&mut buf), &DATA1[..DATA1_LEN - 1]);
+    assert!(client.at_mark().unwrap());
+    assert!(client.recv_oob(&mut buf).is_err());
+    expect_read!(client.read(&mut buf), &DATA1[DATA1_LEN - 1..]);
+    assert!(!client.at_mark().unwrap());
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn wait_for_priority_event(poll: &mut mio::Poll, events: &mut mio::Events) {
+    loop {
+        poll.poll(events, Some(Duration::from_millis(500))).unwrap();
+        assert!(!events.is_empty(), "no priority event");
+        if events
+            .iter()
+            .any(|event| event.token() == ID1 && event.is_priority())
+        {
+            return;
+        }
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn recv_uninit() {
+    use std::mem::MaybeUninit;
+    use util::assume_init;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::READABLE)
+        .unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    let mut buf = [MaybeUninit::uninit(); 20];
+    assert_would_block(stream.recv_uninit(&mut buf));
+
+    peer.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+
+    let n = stream.recv_uninit(&mut buf).unwrap();
+    assert_eq!(unsafe { assume_init(&buf, n) }, DATA1);
+    assert_would_block(stream.recv_uninit(&mut buf));
+
+    drop(peer);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    assert_eq!(stream.recv_uninit(&mut buf).unwrap(), 0);
+}
@@ -0,0 +1,139 @@
+#![cfg(all(feature = "test-util", feature = "net"))]
+
+use std::io::Write;
+use std::net;
+use std::panic;
+
+use mio::checked_write;
+use mio::net::{TcpStream, UdpSocket};
+use mio::test_util::{expect_events, expect_no_events, ExpectEvent, Readiness};
+use mio::{Interest, Token};
+
+mod util;
+use util::{any_local_address, init_with_poll};
+
+const ID1: Token = Token(0);
+const ID2: Token = Token(1);
+
+#[test]
+fn expect_events_found() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&mut stream, ID1, Interest::WRITABLE)
+        .unwrap();
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID2, Interest::WRITABLE)
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![
+            ExpectEvent::new(ID1, Interest::WRITABLE),
+            ExpectEvent::new(ID2, Readiness::WRITABLE),
+        ],
+    );
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+fn expect_events_diagnostics() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    let msg = panic_message(|| {
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Interest::READABLE)],
+        )
+    });
+    assert!(
+        msg.contains("the following expected events were not found:"),
+        "{msg}"
+    );
+    assert!(
+        msg.contains("ExpectEvent { token: Token(0), readiness: READABLE }"),
+        "{msg}"
+    );
+    // Includes the received event, with details.
+    assert!(msg.contains("received events:"), "{msg}");
+    assert!(msg.contains("writable: true"), "{msg}");
+    assert!(msg.contains("details:"), "{msg}");
+}
+
+#[test]
+fn expect_no_events_diagnostics() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::WRITABLE)
+        .unwrap();
+
+    let msg = panic_message(|| expect_no_events(&mut poll, &mut events));
+    assert!(msg.contains("expected no events"), "{msg}");
+    assert!(msg.contains("writable: true"), "{msg}");
+    assert!(msg.contains("details:"), "{msg}");
+}
+
+#[test]
+fn readiness_matches() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket = UdpSocket::bind(any_local_address()).unwrap();
+    poll.registry()
+        .register(&mut socket, ID1, Interest::WRITABLE)
+        .unwrap();
+    poll.poll(&mut events, None).unwrap();
+    let event = events.iter().next().unwrap();
+
+    assert!(Readiness::WRITABLE.matches(event));
+    assert!(!Readiness::READABLE.matches(event));
+    assert!(!(Readiness::WRITABLE | Readiness::ERROR).matches(event));
+    assert!(ExpectEvent::new(ID1, Interest::WRITABLE).matches(event));
+    assert!(!ExpectEvent::new(ID2, Interest::WRITABLE).matches(event));
+}
+
+#[test]
+fn checked_write_macro() {
+    let mut buf = Vec::new();
+    checked_write!(buf.write(b"Hello"));
+    assert_eq!(buf, b"Hello");
+
+    let mut limited = [0; 2];
+    let msg = panic_message(|| {
+        let mut limited = &mut limited[..];
+        checked_write!(limited.write(b"Hello"));
+    });
+    assert!(msg.contains("short write: wrote 2 of 5 bytes"), "{msg}");
+}
+
+#[test]
+#[cfg(not(target_os = "wasi"))]
+fn temp_path_unique() {
+    use mio::test_util::temp_path;
+
+    let path1 = temp_path("socket");
+    let path2 = temp_path("socket");
+    assert_ne!(path1, path2);
+    assert!(path1.to_str().unwrap().ends_with("socket"));
+    assert!(!path1.exists());
+}
+
+/// Returns the panic message of `f`.
+fn panic_message<F: FnOnce()>(f: F) -> String {
+    let err = panic::catch_unwind(panic::AssertUnwindSafe(f)).unwrap_err();
+    match err.downcast::<String>() {
+        Ok(msg) => *msg,
+        Err(err) => err.downcast_ref::<&str>().unwrap().to_string(),
+    }
+}
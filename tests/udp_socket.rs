@@ -73,6 +73,495 @@ fn empty_datagram() {
     expect_read!(s2.recv_from(&mut buf), EMPTY, s1.local_addr().unwrap());
 }
 
+#[test]
+#[cfg(all(
+    any(target_os = "android", target_os = "linux"),
+    // Deregisters the socket after an error event.
+    not(mio_unsupported_force_poll_poll)
+))]
+fn send_zerocopy() {
+    use mio::net::ZerocopyCompletion;
+    use util::Readiness;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address2 = socket2.local_addr().unwrap();
+    assert!(!socket1.zerocopy().unwrap());
+    socket1.set_zerocopy(true).unwrap();
+    assert!(socket1.zerocopy().unwrap());
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+
+    assert_eq!(
+        socket1.send_to_zerocopy(DATA1, address2).unwrap(),
+        DATA1.len()
+    );
+    socket1.connect(address2).unwrap();
+    assert_eq!(socket1.send_zerocopy(DATA2).unwrap(), DATA2.len());
+
+    let mut completions = Vec::new();
+    while completions
+        .iter()
+        .map(ZerocopyCompletion::count)
+        .sum::<u64>()
+        < 2
+    {
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(ID1, Readiness::ERROR)],
+        );
+        for completion in socket1.zerocopy_completions() {
+            completions.push(completion.unwrap());
+        }
+    }
+    assert_eq!(completions[0].first(), 0);
+    assert_eq!(completions.last().unwrap().last(), 1);
+    assert!(completions.iter().all(ZerocopyCompletion::is_copied));
+
+    let mut buf = [0; 20];
+    let n = socket2.recv(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    let n = socket2.recv(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+}
+
+#[test]
+#[cfg(unix)]
+fn send_recv_with_flags() {
+    use mio::net::MsgFlags;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+
+    let flags = MsgFlags::DONTWAIT;
+    assert_eq!(
+        socket1.send_to_with_flags(DATA1, address2, flags).unwrap(),
+        DATA1.len()
+    );
+    socket1.connect(address2).unwrap();
+    assert_eq!(socket1.send_with_flags(DATA2, flags).unwrap(), DATA2.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+
+    // Peeking doesn't remove the datagram, and with `TRUNC` the real length of
+    // the datagram is returned.
+    let mut buf = [0; 5];
+    let flags = MsgFlags::PEEK | MsgFlags::TRUNC;
+    let (n, address) = socket2.recv_from_with_flags(&mut buf, flags).unwrap();
+    assert_eq!(n, DATA1.len());
+    assert_eq!(buf, DATA1[..5]);
+    assert_eq!(address, address1);
+    let n = socket2.recv_with_flags(&mut buf, MsgFlags::TRUNC).unwrap();
+    assert_eq!(n, DATA1.len());
+
+    let mut buf = [0; 20];
+    let (n, address) = socket2
+        .recv_from_with_flags(&mut buf, MsgFlags::EMPTY)
+        .unwrap();
+    assert_eq!(&buf[..n], DATA2);
+    assert_eq!(address, address1);
+    assert_would_block(socket2.recv_with_flags(&mut buf, MsgFlags::EMPTY));
+}
+
+#[test]
+#[cfg(unix)]
+fn recv_uninit() {
+    use std::mem::MaybeUninit;
+    use util::assume_init;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+
+    let mut buf = [MaybeUninit::uninit(); 20];
+    assert_would_block(socket2.recv_from_uninit(&mut buf));
+
+    checked_write!(socket1.send_to(DATA1, socket2.local_addr().unwrap()));
+    checked_write!(socket1.send_to(DATA2, socket2.local_addr().unwrap()));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+
+    let (n, address) = socket2.recv_from_uninit(&mut buf).unwrap();
+    assert_eq!(unsafe { assume_init(&buf, n) }, DATA1);
+    assert_eq!(address, address1);
+    socket2.connect(address1).unwrap();
+    let n = socket2.recv_uninit(&mut buf).unwrap();
+    assert_eq!(unsafe { assume_init(&buf, n) }, DATA2);
+    assert_would_block(socket2.recv_uninit(&mut buf));
+}
+
+#[test]
+#[cfg(unix)]
+fn send_recv_many() {
+    use mio::net::{RecvDatagram, SendDatagram};
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket3 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+    let address3 = socket3.local_addr().unwrap();
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut socket3, ID3, Interest::READABLE)
+        .unwrap();
+
+    let mut bufs = [[0; 20]; 4];
+    let mut msgs = bufs
+        .iter_mut()
+        .map(|buf| RecvDatagram::new(buf))
+        .collect::<Vec<_>>();
+    assert_would_block(socket2.recv_many(&mut msgs));
+
+    socket1.connect(address2).unwrap();
+    let n = socket1
+        .send_many(&[
+            SendDatagram::to(DATA1, address2),
+            SendDatagram::to(DATA2, address3),
+            SendDatagram::new(DATA2),
+            SendDatagram::new(b""),
+        ])
+        .unwrap();
+    assert_eq!(n, 4);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![
+            ExpectEvent::new(ID2, Interest::READABLE),
+            ExpectEvent::new(ID3, Interest::READABLE),
+        ],
+    );
+
+    assert_eq!(socket2.recv_many(&mut msgs).unwrap(), 3);
+    assert_eq!(msgs[0].data(), DATA1);
+    assert_eq!(msgs[1].data(), DATA2);
+    assert!(msgs[2].is_empty());
+    for msg in &msgs[..3] {
+        assert_eq!(msg.addr(), Some(&address1));
+        assert!(!msg.is_truncated());
+    }
+    assert_eq!(msgs[3].addr(), None);
+    assert_would_block(socket2.recv_many(&mut msgs));
+    // Datagrams received by a previous call are reset.
+    assert!(msgs
+        .iter()
+        .all(|msg| msg.is_empty() && msg.addr().is_none()));
+
+    // Datagrams larger than the buffer are truncated.
+    let mut buf = [0; 5];
+    let mut msgs = [RecvDatagram::new(&mut buf)];
+    assert_eq!(socket3.recv_many(&mut msgs).unwrap(), 1);
+    assert_eq!(msgs[0].data(), &DATA2[..5]);
+    assert_eq!(msgs[0].addr(), Some(&address1));
+    assert!(msgs[0].is_truncated());
+    assert_would_block(socket3.recv_many(&mut msgs));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn send_recv_segmented() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let mut socket3 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+    assert!(!socket2.gro().unwrap());
+    socket2.set_gro(true).unwrap();
+    assert!(socket2.gro().unwrap());
+    poll.registry()
+        .register(&mut socket2, ID2, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut socket3, ID3, Interest::READABLE)
+        .unwrap();
+
+    // Three full segments and a smaller one.
+    let data = (0..350).map(|i| i as u8).collect::<Vec<u8>>();
+    let n = socket1.send_to_segmented(&data, address2, 100).unwrap();
+    assert_eq!(n, data.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+    // With GRO enabled the datagrams are received as a single buffer.
+    let mut buf = vec![0; 1 << 16];
+    let (n, address, segment_size) = socket2.recv_from_segmented(&mut buf).unwrap();
+    assert_eq!(&buf[..n], &data[..]);
+    assert_eq!(address, address1);
+    assert_eq!(segment_size, Some(100));
+    assert_would_block(socket2.recv_from_segmented(&mut buf));
+
+    // Without GRO the datagrams are received separately.
+    socket1.connect(socket3.local_addr().unwrap()).unwrap();
+    let n = socket1.send_segmented(&data, 100).unwrap();
+    assert_eq!(n, data.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID3, Interest::READABLE)],
+    );
+    for segment in data.chunks(100) {
+        let (n, address, segment_size) = socket3.recv_from_segmented(&mut buf).unwrap();
+        assert_eq!(&buf[..n], segment);
+        assert_eq!(address, address1);
+        assert_eq!(segment_size, None);
+    }
+    assert_would_block(socket3.recv_from_segmented(&mut buf));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn send_recv_msg() {
+    use mio::net::{ControlMessage, RecvControl, SendControl};
+    use std::io;
+    use std::net::Ipv4Addr;
+    use std::time::SystemTime;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    // Bound to an unspecified address, as a multi-homed server would be.
+    let mut server = UdpSocket::bind("0.0.0.0:0".parse().unwrap()).unwrap();
+    let mut client = UdpSocket::bind(any_local_address()).unwrap();
+    let client_address = client.local_addr().unwrap();
+    // Any address in 127.0.0.0/8 is routed to the loopback interface.
+    let destination = Ipv4Addr::new(127, 0, 0, 2);
+    let server_address = SocketAddr::new(destination.into(), server.local_addr().unwrap().port());
+    assert!(!server.recv_pktinfo().unwrap());
+    assert!(!server.recv_ttl().unwrap());
+    assert!(!server.recv_timestamp().unwrap());
+    server.set_recv_pktinfo(true).unwrap();
+    server.set_recv_ttl(true).unwrap();
+    server.set_recv_timestamp(true).unwrap();
+    assert!(server.recv_pktinfo().unwrap());
+    assert!(server.recv_ttl().unwrap());
+    assert!(server.recv_timestamp().unwrap());
+    poll.registry()
+        .register(&mut server, ID1, Interest::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&mut client, ID2, Interest::READABLE)
+        .unwrap();
+
+    let before = SystemTime::now();
+    let mut control = SendControl::new();
+    assert!(control.is_empty());
+    control.push(ControlMessage::Ttl(16)).unwrap();
+    assert!(control
+        .push(ControlMessage::Overflow(0))
+        .is_err_and(|err| err.kind() == io::ErrorKind::InvalidInput));
+    assert_eq!(
+        client.send_to_msg(DATA1, server_address, &control).unwrap(),
+        DATA1.len()
+    );
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; 20];
+    let mut control = RecvControl::new();
+    let (n, address) = server.recv_msg(&mut buf, &mut control).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(address, client_address);
+    assert!(!control.is_truncated());
+    let mut interface = None;
+    let mut received = None;
+    let mut ttl = None;
+    for message in control.messages() {
+        match message {
+            ControlMessage::PacketInfo { addr, interface: i } => {
+                assert_eq!(addr, IpAddr::V4(destination));
+                interface = Some(i);
+            }
+            ControlMessage::Timestamp(timestamp) => received = Some(timestamp),
+            ControlMessage::Ttl(t) => ttl = Some(t),
+            message => panic!("unexpected control message: {message:?}"),
+        }
+    }
+    let interface = interface.expect("missing packet info");
+    assert_ne!(interface, 0);
+    assert!(received.expect("missing timestamp") >= before);
+    assert_eq!(ttl, Some(16));
+    assert_would_block(server.recv_msg(&mut buf, &mut control));
+
+    // Reply from the address the request was sent to.
+    let mut control = SendControl::new();
+    control
+        .push(ControlMessage::PacketInfo {
+            addr: IpAddr::V4(destination),
+            interface,
+        })
+        .unwrap();
+    assert_eq!(
+        server.send_to_msg(DATA2, address, &control).unwrap(),
+        DATA2.len()
+    );
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interest::READABLE)],
+    );
+    let mut control = RecvControl::new();
+    let (n, address) = client.recv_msg(&mut buf, &mut control).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+    assert_eq!(address, server_address);
+    assert_eq!(control.messages().count(), 0);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn send_recv_msg_ipv6() {
+    use mio::net::{ControlMessage, RecvControl, SendControl};
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+    socket1.set_recv_pktinfo(true).unwrap();
+    socket1.set_recv_ttl(true).unwrap();
+    assert!(socket1.recv_pktinfo().unwrap());
+    assert!(socket1.recv_ttl().unwrap());
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+
+    let mut control = SendControl::new();
+    control
+        .push(ControlMessage::PacketInfo {
+            addr: address2.ip(),
+            interface: 0,
+        })
+        .unwrap();
+    control.push(ControlMessage::HopLimit(8)).unwrap();
+    socket2.connect(address1).unwrap();
+    assert_eq!(socket2.send_msg(DATA1, &control).unwrap(), DATA1.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let mut buf = [0; 20];
+    let mut control = RecvControl::new();
+    let (n, address) = socket1.recv_msg(&mut buf, &mut control).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(address, address2);
+    let mut messages = control.messages().collect::<Vec<_>>();
+    messages.sort_by_key(|message| matches!(message, ControlMessage::HopLimit(_)));
+    match messages.as_slice() {
+        [ControlMessage::PacketInfo { addr, interface }, ControlMessage::HopLimit(8)] => {
+            assert_eq!(*addr, address1.ip());
+            assert_ne!(*interface, 0);
+        }
+        messages => panic!("unexpected control messages: {messages:?}"),
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn recv_msg_overflow() {
+    use mio::net::{ControlMessage, RecvControl};
+    use std::io;
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    assert!(!socket1.recv_overflow().unwrap());
+    socket1.set_recv_overflow(true).unwrap();
+    assert!(socket1.recv_overflow().unwrap());
+    // Use the smallest receive buffer possible so it overflows quickly.
+    let size: libc::c_int = 0;
+    // SAFETY: `socket1` is a valid socket.
+    let res = unsafe {
+        libc::setsockopt(
+            socket1.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_RCVBUF,
+            (&size as *const libc::c_int).cast(),
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    assert_eq!(res, 0);
+    poll.registry()
+        .register(&mut socket1, ID1, Interest::READABLE)
+        .unwrap();
+
+    let data = [0; 1000];
+    for _ in 0..64 {
+        socket2.send_to(&data, address1).unwrap();
+    }
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    // The datagrams queued before the buffer was full have no drops.
+    let mut buf = [0; 1000];
+    let mut control = RecvControl::new();
+    let mut received = 0;
+    loop {
+        match socket1.recv_msg(&mut buf, &mut control) {
+            Ok(_) => received += 1,
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+            Err(err) => panic!("unexpected error: {err}"),
+        }
+        assert_eq!(control.messages().count(), 0);
+    }
+    assert!(received < 64);
+
+    // The next datagram reports the number of datagrams dropped.
+    socket2.send_to(DATA1, address1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interest::READABLE)],
+    );
+    let (n, _) = socket1.recv_msg(&mut buf, &mut control).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    let messages = control.messages().collect::<Vec<_>>();
+    assert_eq!(messages, [ControlMessage::Overflow(64 - received)]);
+}
+
 #[test]
 fn is_send_and_sync() {
     assert_send::<UdpSocket>();
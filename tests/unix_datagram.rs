@@ -10,7 +10,7 @@ use std::os::unix::net;
 mod util;
 use util::{
     assert_send, assert_socket_close_on_exec, assert_socket_non_blocking, assert_sync,
-    assert_would_block, expect_events, expect_no_events, init, init_with_poll, temp_file,
+    assert_would_block, expect_events, expect_no_events, init, init_with_poll, temp_path,
     ExpectEvent, Readiness,
 };
 
@@ -37,8 +37,8 @@ fn is_send_and_sync() {
 )]
 fn unix_datagram_smoke_unconnected() {
     init();
-    let path1 = temp_file("unix_datagram_smoke_unconnected1");
-    let path2 = temp_file("unix_datagram_smoke_unconnected2");
+    let path1 = temp_path("unix_datagram_smoke_unconnected1");
+    let path2 = temp_path("unix_datagram_smoke_unconnected2");
 
     let datagram1 = UnixDatagram::bind(path1).unwrap();
     let datagram2 = UnixDatagram::bind(path2).unwrap();
@@ -48,8 +48,8 @@ fn unix_datagram_smoke_unconnected() {
 #[test]
 fn unix_datagram_smoke_connected() {
     init();
-    let path1 = temp_file("unix_datagram_smoke_connected1");
-    let path2 = temp_file("unix_datagram_smoke_connected2");
+    let path1 = temp_path("unix_datagram_smoke_connected1");
+    let path2 = temp_path("unix_datagram_smoke_connected2");
 
     let datagram1 = UnixDatagram::bind(&path1).unwrap();
     let datagram2 = UnixDatagram::bind(&path2).unwrap();
@@ -70,8 +70,8 @@ fn unix_datagram_smoke_connected() {
 )]
 fn unix_datagram_smoke_unconnected_from_std() {
     init();
-    let path1 = temp_file("unix_datagram_smoke_unconnected_from_std1");
-    let path2 = temp_file("unix_datagram_smoke_unconnected_from_std2");
+    let path1 = temp_path("unix_datagram_smoke_unconnected_from_std1");
+    let path2 = temp_path("unix_datagram_smoke_unconnected_from_std2");
 
     let datagram1 = net::UnixDatagram::bind(path1).unwrap();
     let datagram2 = net::UnixDatagram::bind(path2).unwrap();
@@ -87,8 +87,8 @@ fn unix_datagram_smoke_unconnected_from_std() {
 #[test]
 fn unix_datagram_smoke_connected_from_std() {
     init();
-    let path1 = temp_file("unix_datagram_smoke_connected_from_std1");
-    let path2 = temp_file("unix_datagram_smoke_connected_from_std2");
+    let path1 = temp_path("unix_datagram_smoke_connected_from_std1");
+    let path2 = temp_path("unix_datagram_smoke_connected_from_std2");
 
     let datagram1 = net::UnixDatagram::bind(&path1).unwrap();
     let datagram2 = net::UnixDatagram::bind(&path2).unwrap();
@@ -111,8 +111,8 @@ fn unix_datagram_smoke_connected_from_std() {
 )]
 fn unix_datagram_connect() {
     init();
-    let path1 = temp_file("unix_datagram_connect1");
-    let path2 = temp_file("unix_datagram_connect2");
+    let path1 = temp_path("unix_datagram_connect1");
+    let path2 = temp_path("unix_datagram_connect2");
 
     let datagram1 = UnixDatagram::bind(path1).unwrap();
     let datagram1_local = datagram1.local_addr().unwrap();
@@ -135,6 +135,151 @@ fn unix_datagram_connect() {
         .unwrap();
 }
 
+#[test]
+#[cfg_attr(
+    target_os = "hurd",
+    ignore = "getting pathname isn't supported on GNU/Hurd"
+)]
+#[cfg_attr(
+    target_os = "cygwin",
+    ignore = "getting pathname isn't supported on Cygwin"
+)]
+fn unix_datagram_send_recv_with_flags() {
+    use mio::net::MsgFlags;
+
+    init();
+    let path1 = temp_path("unix_datagram_send_recv_with_flags1");
+    let path2 = temp_path("unix_datagram_send_recv_with_flags2");
+
+    let datagram1 = UnixDatagram::bind(&path1).unwrap();
+    let datagram2 = UnixDatagram::bind(&path2).unwrap();
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_would_block(datagram2.recv_from_with_flags(&mut buf, MsgFlags::EMPTY));
+
+    let n = datagram1
+        .send_to_with_flags(DATA1, &path2, MsgFlags::DONTWAIT)
+        .unwrap();
+    assert_eq!(n, DATA1.len());
+
+    // With `TRUNC` the real length of the datagram is returned.
+    let mut small_buf = [0; 5];
+    let (n, address) = datagram2
+        .recv_from_with_flags(&mut small_buf, MsgFlags::PEEK | MsgFlags::TRUNC)
+        .unwrap();
+    assert_eq!(n, DATA1.len());
+    assert_eq!(small_buf, DATA1[..5]);
+    assert_eq!(address.as_pathname(), Some(&*path1));
+    let (n, address) = datagram2
+        .recv_from_with_flags(&mut buf, MsgFlags::EMPTY)
+        .unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(address.as_pathname(), Some(&*path1));
+
+    // Unnamed sockets.
+    let (datagram1, datagram2) = UnixDatagram::pair().unwrap();
+    let n = datagram1.send_with_flags(DATA2, MsgFlags::EMPTY).unwrap();
+    assert_eq!(n, DATA2.len());
+    let (n, address) = datagram2
+        .recv_from_with_flags(&mut buf, MsgFlags::EMPTY)
+        .unwrap();
+    assert_eq!(&buf[..n], DATA2);
+    assert!(address.is_unnamed());
+    assert_would_block(datagram2.recv_with_flags(&mut buf, MsgFlags::EMPTY));
+}
+
+#[test]
+#[cfg_attr(
+    target_os = "hurd",
+    ignore = "getting pathname isn't supported on GNU/Hurd"
+)]
+#[cfg_attr(
+    target_os = "cygwin",
+    ignore = "getting pathname isn't supported on Cygwin"
+)]
+fn unix_datagram_recv_uninit() {
+    use std::mem::MaybeUninit;
+    use util::assume_init;
+
+    init();
+    let path1 = temp_path("unix_datagram_recv_uninit1");
+    let path2 = temp_path("unix_datagram_recv_uninit2");
+
+    let datagram1 = UnixDatagram::bind(&path1).unwrap();
+    let datagram2 = UnixDatagram::bind(&path2).unwrap();
+
+    let mut buf = [MaybeUninit::uninit(); DEFAULT_BUF_SIZE];
+    assert_would_block(datagram2.recv_from_uninit(&mut buf));
+
+    checked_write!(datagram1.send_to(DATA1, &path2));
+    checked_write!(datagram1.send_to(DATA2, &path2));
+
+    let (n, address) = datagram2.recv_from_uninit(&mut buf).unwrap();
+    assert_eq!(unsafe { assume_init(&buf, n) }, DATA1);
+    assert_eq!(address.as_pathname(), Some(&*path1));
+    let n = datagram2.recv_uninit(&mut buf).unwrap();
+    assert_eq!(unsafe { assume_init(&buf, n) }, DATA2);
+    assert_would_block(datagram2.recv_uninit(&mut buf));
+}
+
+#[test]
+#[cfg_attr(
+    target_os = "hurd",
+    ignore = "getting pathname isn't supported on GNU/Hurd"
+)]
+#[cfg_attr(
+    target_os = "cygwin",
+    ignore = "getting pathname isn't supported on Cygwin"
+)]
+fn unix_datagram_send_recv_many() {
+    use mio::net::{RecvDatagram, SendDatagram};
+    use std::os::unix::net::SocketAddr;
+
+    init();
+    let path1 = temp_path("unix_datagram_send_recv_many1");
+    let path2 = temp_path("unix_datagram_send_recv_many2");
+
+    let datagram1 = UnixDatagram::bind(&path1).unwrap();
+    let datagram2 = UnixDatagram::bind(&path2).unwrap();
+    let address2 = SocketAddr::from_pathname(&path2).unwrap();
+
+    let mut bufs = [[0; DEFAULT_BUF_SIZE]; 3];
+    let mut msgs = bufs
+        .iter_mut()
+        .map(|buf| RecvDatagram::new(buf))
+        .collect::<Vec<_>>();
+    assert_would_block(datagram2.recv_many(&mut msgs));
+
+    let n = datagram1
+        .send_many(&[
+            SendDatagram::to(DATA1, address2.clone()),
+            SendDatagram::to(DATA2, address2),
+        ])
+        .unwrap();
+    assert_eq!(n, 2);
+
+    assert_eq!(datagram2.recv_many(&mut msgs).unwrap(), 2);
+    assert_eq!(msgs[0].data(), DATA1);
+    assert_eq!(msgs[1].data(), DATA2);
+    for msg in &msgs[..2] {
+        assert_eq!(msg.addr().unwrap().as_pathname(), Some(&*path1));
+        assert!(!msg.is_truncated());
+    }
+    assert!(msgs[2].addr().is_none());
+    assert_would_block(datagram2.recv_many(&mut msgs));
+
+    // Connected, unnamed sockets.
+    let (datagram1, datagram2) = UnixDatagram::pair().unwrap();
+    let n = datagram1.send_many(&[SendDatagram::new(DATA1)]).unwrap();
+    assert_eq!(n, 1);
+    let mut buf = [0; 5];
+    let mut msgs = [RecvDatagram::new(&mut buf)];
+    assert_eq!(datagram2.recv_many(&mut msgs).unwrap(), 1);
+    assert_eq!(msgs[0].data(), &DATA1[..5]);
+    assert!(msgs[0].addr().unwrap().is_unnamed());
+    assert!(msgs[0].is_truncated());
+}
+
 #[test]
 fn unix_datagram_pair() {
     let (mut poll, mut events) = init_with_poll();
@@ -192,8 +337,8 @@ fn unix_datagram_pair() {
 #[cfg_attr(target_os = "cygwin", ignore = "POLLRDHUP isn't supported on Cygwin")]
 fn unix_datagram_shutdown() {
     let (mut poll, mut events) = init_with_poll();
-    let path1 = temp_file("unix_datagram_shutdown1");
-    let path2 = temp_file("unix_datagram_shutdown2");
+    let path1 = temp_path("unix_datagram_shutdown1");
+    let path2 = temp_path("unix_datagram_shutdown2");
 
     let mut datagram1 = UnixDatagram::bind(path1).unwrap();
     let mut datagram2 = UnixDatagram::bind(&path2).unwrap();
@@ -250,7 +395,7 @@ fn unix_datagram_shutdown() {
 #[test]
 fn unix_datagram_register() {
     let (mut poll, mut events) = init_with_poll();
-    let path = temp_file("unix_datagram_register");
+    let path = temp_path("unix_datagram_register");
 
     let mut datagram = UnixDatagram::bind(path).unwrap();
     poll.registry()
@@ -262,8 +407,8 @@ fn unix_datagram_register() {
 #[test]
 fn unix_datagram_reregister() {
     let (mut poll, mut events) = init_with_poll();
-    let path1 = temp_file("unix_datagram_reregister1");
-    let path2 = temp_file("unix_datagram_reregister2");
+    let path1 = temp_path("unix_datagram_reregister1");
+    let path2 = temp_path("unix_datagram_reregister2");
 
     let mut datagram1 = UnixDatagram::bind(&path1).unwrap();
     poll.registry()
@@ -285,8 +430,8 @@ fn unix_datagram_reregister() {
 #[test]
 fn unix_datagram_deregister() {
     let (mut poll, mut events) = init_with_poll();
-    let path1 = temp_file("unix_datagram_deregister1");
-    let path2 = temp_file("unix_datagram_deregister2");
+    let path1 = temp_path("unix_datagram_deregister1");
+    let path2 = temp_path("unix_datagram_deregister2");
 
     let mut datagram1 = UnixDatagram::bind(&path1).unwrap();
     poll.registry()
@@ -1,6 +1,6 @@
 #![cfg(all(unix, feature = "os-poll", feature = "net", not(miri)))] // Miri doesn't support Unix domain sockets.
 
-use mio::net::UnixListener;
+use mio::net::{AcceptReserve, Accepted, UnixListener};
 use mio::{Interest, Token};
 use std::io::{self, Read};
 use std::os::unix::net;
@@ -12,7 +12,7 @@ use std::thread;
 mod util;
 use util::{
     assert_send, assert_socket_close_on_exec, assert_socket_non_blocking, assert_sync,
-    assert_would_block, expect_events, expect_no_events, init_with_poll, temp_file, ExpectEvent,
+    assert_would_block, expect_events, expect_no_events, init_with_poll, temp_path, ExpectEvent,
 };
 
 const DEFAULT_BUF_SIZE: usize = 64;
@@ -44,12 +44,50 @@ fn unix_listener_from_std() {
     )
 }
 
+#[test]
+fn unix_listener_accept_many() {
+    let path = temp_path("unix_listener_accept_many");
+    let listener = UnixListener::bind(&path).unwrap();
+    let _streams: Vec<net::UnixStream> = (0..3)
+        .map(|_| net::UnixStream::connect(&path).unwrap())
+        .collect();
+
+    let mut accepted = Vec::new();
+    assert!(listener.accept_many(&mut accepted, 2).unwrap().is_limit());
+    assert_eq!(accepted.len(), 2);
+    assert!(listener
+        .accept_many(&mut accepted, 10)
+        .unwrap()
+        .is_would_block());
+    assert_eq!(accepted.len(), 3);
+    for (stream, _) in &accepted {
+        assert_socket_non_blocking(stream);
+        assert_socket_close_on_exec(stream);
+    }
+}
+
+#[test]
+fn unix_listener_accept_reserved() {
+    let path = temp_path("unix_listener_accept_reserved");
+    let listener = UnixListener::bind(&path).unwrap();
+    let mut reserve = AcceptReserve::new().unwrap();
+    assert!(reserve.is_reserved());
+
+    let _stream = net::UnixStream::connect(&path).unwrap();
+    match listener.accept_reserved(&mut reserve).unwrap() {
+        Accepted::Stream(stream, _) => assert_socket_non_blocking(&stream),
+        accepted => panic!("unexpected result: {accepted:?}"),
+    }
+    assert_would_block(listener.accept_reserved(&mut reserve));
+    assert!(reserve.is_reserved());
+}
+
 #[test]
 fn unix_listener_local_addr() {
     let (mut poll, mut events) = init_with_poll();
     let barrier = Arc::new(Barrier::new(2));
 
-    let path = temp_file("unix_listener_local_addr");
+    let path = temp_path("unix_listener_local_addr");
     let mut listener = UnixListener::bind(&path).unwrap();
     poll.registry()
         .register(
@@ -80,7 +118,7 @@ fn unix_listener_local_addr() {
 fn unix_listener_register() {
     let (mut poll, mut events) = init_with_poll();
 
-    let path = temp_file("unix_listener_register");
+    let path = temp_path("unix_listener_register");
     let mut listener = UnixListener::bind(path).unwrap();
     poll.registry()
         .register(&mut listener, TOKEN_1, Interest::READABLE)
@@ -93,7 +131,7 @@ fn unix_listener_reregister() {
     let (mut poll, mut events) = init_with_poll();
     let barrier = Arc::new(Barrier::new(2));
 
-    let path = temp_file("unix_listener_reregister");
+    let path = temp_path("unix_listener_reregister");
     let mut listener = UnixListener::bind(&path).unwrap();
     poll.registry()
         .register(&mut listener, TOKEN_1, Interest::WRITABLE)
@@ -123,7 +161,7 @@ fn unix_listener_deregister() {
     let (mut poll, mut events) = init_with_poll();
     let barrier = Arc::new(Barrier::new(2));
 
-    let path = temp_file("unix_listener_deregister");
+    let path = temp_path("unix_listener_deregister");
     let mut listener = UnixListener::bind(&path).unwrap();
     poll.registry()
         .register(&mut listener, TOKEN_1, Interest::READABLE)
@@ -193,7 +231,7 @@ where
 {
     let (mut poll, mut events) = init_with_poll();
     let barrier = Arc::new(Barrier::new(2));
-    let path = temp_file(test_name);
+    let path = temp_path(test_name);
 
     let mut listener = new_listener(&path).unwrap();
 
@@ -225,3 +225,76 @@ pub fn expect_one_closed_event(poll: &mut Poll, events: &mut Events, token: Toke
     }
     assert!(iter.next().is_none());
 }
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn splice_socket() {
+    use std::os::unix::net::UnixStream;
+
+    let (sender, receiver) = pipe::new().unwrap();
+    let (mut source, source_peer) = UnixStream::pair().unwrap();
+    let (destination, mut destination_peer) = UnixStream::pair().unwrap();
+    source_peer.set_nonblocking(true).unwrap();
+    destination.set_nonblocking(true).unwrap();
+
+    assert_would_block(sender.splice_from(&source_peer, 1024));
+    assert_would_block(receiver.splice_to(&destination, 1024));
+
+    // Socket -> pipe -> socket.
+    source.write_all(DATA1).unwrap();
+    assert_eq!(sender.splice_from(&source_peer, 5).unwrap(), 5);
+    assert_eq!(
+        sender.splice_from(&source_peer, 1024).unwrap(),
+        DATA1.len() - 5
+    );
+    assert_eq!(receiver.splice_to(&destination, 1024).unwrap(), DATA1.len());
+    let mut buf = [0; DATA1.len()];
+    destination_peer.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, DATA1);
+
+    // End of file.
+    drop(source);
+    assert_eq!(sender.splice_from(&source_peer, 1024).unwrap(), 0);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn tee() {
+    let (mut sender1, receiver1) = pipe::new().unwrap();
+    let (sender2, mut receiver2) = pipe::new().unwrap();
+
+    assert_would_block(receiver1.tee(&sender2, 1024));
+
+    sender1.write_all(DATA1).unwrap();
+    assert_eq!(receiver1.tee(&sender2, 1024).unwrap(), DATA1.len());
+
+    // The data is in both pipes.
+    let mut buf = [0; DATA1.len()];
+    receiver2.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, DATA1);
+    let mut buf = [0; DATA1.len()];
+    (&receiver1).read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, DATA1);
+
+    assert_would_block(receiver1.tee(&sender2, 1024));
+}
+
+#[test]
+fn recv_uninit() {
+    use std::mem::MaybeUninit;
+    use util::assume_init;
+
+    let (mut sender, receiver) = pipe::new().unwrap();
+
+    let mut buf = [MaybeUninit::uninit(); 20];
+    assert_would_block(receiver.recv_uninit(&mut buf));
+
+    let n = sender.write(DATA1).unwrap();
+    assert_eq!(n, DATA1.len());
+    let n = receiver.recv_uninit(&mut buf).unwrap();
+    assert_eq!(unsafe { assume_init(&buf, n) }, DATA1);
+    assert_would_block(receiver.recv_uninit(&mut buf));
+
+    drop(sender);
+    assert_eq!(receiver.recv_uninit(&mut buf).unwrap(), 0);
+}
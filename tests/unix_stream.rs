@@ -14,7 +14,7 @@ use std::thread;
 mod util;
 use util::{
     assert_send, assert_socket_close_on_exec, assert_socket_non_blocking, assert_sync,
-    assert_would_block, expect_events, expect_no_events, init, init_with_poll, temp_file,
+    assert_would_block, expect_events, expect_no_events, init, init_with_poll, temp_path,
     ExpectEvent, Readiness,
 };
 
@@ -46,7 +46,7 @@ fn unix_stream_smoke() {
 fn unix_stream_connect() {
     let (mut poll, mut events) = init_with_poll();
     let barrier = Arc::new(Barrier::new(2));
-    let path = temp_file("unix_stream_connect");
+    let path = temp_path("unix_stream_connect");
 
     let listener = net::UnixListener::bind(path.clone()).unwrap();
     let mut stream = UnixStream::connect(path).unwrap();
@@ -90,7 +90,7 @@ fn unix_stream_connect_addr() {
     let (mut poll, mut events) = init_with_poll();
     let barrier = Arc::new(Barrier::new(2));
 
-    let path = temp_file("unix_stream_connect_addr");
+    let path = temp_path("unix_stream_connect_addr");
     let listener = net::UnixListener::bind(path.clone()).unwrap();
     let mio_listener = mio::net::UnixListener::from_std(listener);
 
@@ -178,6 +178,75 @@ fn unix_stream_pair() {
     assert_would_block(s2.read(&mut buf));
 }
 
+#[test]
+#[cfg_attr(
+    target_os = "hurd",
+    ignore = "getting pathname isn't supported on GNU/Hurd"
+)]
+fn unix_stream_connect_status() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let path = temp_path("unix_stream_connect_status");
+    let listener = net::UnixListener::bind(&path).unwrap();
+    let mut stream = UnixStream::connect(&path).unwrap();
+    poll.registry()
+        .register(&mut stream, TOKEN_1, Interest::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interest::WRITABLE)],
+    );
+    assert!(stream.connect_status().unwrap().is_connected());
+    drop(listener);
+
+    let (s1, _s2) = UnixStream::pair().unwrap();
+    assert!(s1.connect_status().unwrap().is_connected());
+}
+
+#[test]
+fn unix_stream_send_recv_with_flags() {
+    use mio::net::MsgFlags;
+
+    let (s1, s2) = UnixStream::pair().unwrap();
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_would_block(s2.recv_with_flags(&mut buf, MsgFlags::PEEK));
+
+    assert_eq!(
+        s1.send_with_flags(DATA1, MsgFlags::DONTWAIT).unwrap(),
+        DATA1_LEN
+    );
+    expect_read!(s2.recv_with_flags(&mut buf, MsgFlags::PEEK), DATA1);
+    expect_read!(s2.recv_with_flags(&mut buf, MsgFlags::EMPTY), DATA1);
+    assert_would_block(s2.recv_with_flags(&mut buf, MsgFlags::EMPTY));
+}
+
+#[test]
+fn unix_stream_recv_uninit() {
+    use std::mem::MaybeUninit;
+    use util::assume_init;
+
+    let (mut s1, s2) = UnixStream::pair().unwrap();
+    let mut buf = [MaybeUninit::uninit(); DEFAULT_BUF_SIZE];
+    assert_would_block(s2.recv_uninit(&mut buf));
+
+    checked_write!(s1.write(DATA1));
+    let n = s2.recv_uninit(&mut buf).unwrap();
+    assert_eq!(unsafe { assume_init(&buf, n) }, DATA1);
+    assert_would_block(s2.recv_uninit(&mut buf));
+
+    drop(s1);
+    assert_eq!(s2.recv_uninit(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn unix_stream_recv_lowat() {
+    let (s1, _s2) = UnixStream::pair().unwrap();
+    assert_eq!(s1.recv_lowat().unwrap(), 1);
+    s1.set_recv_lowat(DATA1_LEN as u32).unwrap();
+    assert_eq!(s1.recv_lowat().unwrap(), DATA1_LEN as u32);
+}
+
 #[test]
 #[cfg_attr(
     target_os = "hurd",
@@ -610,7 +679,7 @@ fn new_echo_listener(
 ) -> (thread::JoinHandle<()>, net::SocketAddr) {
     let (addr_sender, addr_receiver) = channel();
     let handle = thread::spawn(move || {
-        let path = temp_file(test_name);
+        let path = temp_path(test_name);
         let listener = net::UnixListener::bind(path).unwrap();
         let local_addr = listener.local_addr().unwrap();
         addr_sender.send(local_addr).unwrap();
@@ -663,7 +732,7 @@ fn new_noop_listener(
 ) -> (thread::JoinHandle<()>, net::SocketAddr) {
     let (sender, receiver) = channel();
     let handle = thread::spawn(move || {
-        let path = temp_file(test_name);
+        let path = temp_path(test_name);
         let listener = net::UnixListener::bind(path).unwrap();
         let local_addr = listener.local_addr().unwrap();
         sender.send(local_addr).unwrap();
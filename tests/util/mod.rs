@@ -4,6 +4,7 @@
 
 #[cfg(not(target_os = "wasi"))]
 use std::mem::size_of;
+use std::mem::MaybeUninit;
 use std::net::SocketAddr;
 use std::ops::BitOr;
 #[cfg(any(unix, target_os = "wasi"))]
@@ -12,7 +13,7 @@ use std::os::fd::AsRawFd;
 use std::path::PathBuf;
 use std::sync::Once;
 use std::time::Duration;
-#[cfg(not(target_os = "wasi"))]
+#[cfg(unix)]
 use std::{env, fs};
 use std::{fmt, io};
 
@@ -22,22 +23,15 @@ use mio::event::Event;
 use mio::net::TcpStream;
 use mio::{Events, Interest, Poll, Token};
 
+#[cfg(not(target_os = "wasi"))]
+#[path = "../../src/test_util/temp.rs"]
+mod temp;
+
 pub fn init() {
     static INIT: Once = Once::new();
 
     INIT.call_once(|| {
         env_logger::try_init().expect("unable to initialise logger");
-
-        // Each test is run in a sandbox on WASI (and `temp_dir`
-        // doesn't work as of this writing, anyway), so no need to
-        // clean up here.
-        #[cfg(not(target_os = "wasi"))]
-        {
-            // Remove all temporary files from previous test runs.
-            let dir = temp_dir();
-            let _ = fs::remove_dir_all(&dir);
-            fs::create_dir_all(&dir).expect("unable to create temporary directory");
-        }
     })
 }
 
@@ -318,20 +312,70 @@ pub fn set_linger_zero(socket: &TcpStream) {
     );
 }
 
-/// Returns a path to a temporary file using `name` as filename.
+/// Returns a unique path in the temporary directory, same as
+/// `mio::test_util::temp_path` (which requires the `test-util` feature).
 #[cfg(not(target_os = "wasi"))]
-pub fn temp_file(name: &'static str) -> PathBuf {
-    let mut path = temp_dir();
-    path.push(name);
-    path
+pub fn temp_path(name: &str) -> PathBuf {
+    temp::temp_path(name)
 }
 
-/// Returns the temporary directory for Mio test files.
-#[cfg(not(target_os = "wasi"))]
-fn temp_dir() -> PathBuf {
-    let mut path = env::temp_dir();
-    path.push("mio_tests");
-    path
+/// Runs the test `name` in a new process, for tests that change process wide
+/// state such as resource limits.
+///
+/// Returns true if called in the new process, in which case the test should be
+/// run. Otherwise it waits for the process and panics if the test failed.
+#[cfg(unix)]
+pub fn in_subprocess(name: &str) -> bool {
+    const ENV: &str = "MIO_TEST_SUBPROCESS";
+    if env::var_os(ENV).is_some_and(|test| test == name) {
+        return true;
+    }
+    let status = std::process::Command::new(env::current_exe().unwrap())
+        .args([name, "--exact", "--test-threads=1", "--nocapture"])
+        .env(ENV, name)
+        .status()
+        .unwrap();
+    assert!(
+        status.success(),
+        "test {name} failed in subprocess: {status}"
+    );
+    false
+}
+
+/// Lowers the soft limit of open file descriptors to the number currently in
+/// use, so opening the next file descriptor fails with `EMFILE`. Returns the
+/// old limit, to be restored using `set_fd_limit`.
+///
+/// Should only be used in tests run using `in_subprocess`.
+#[cfg(unix)]
+pub fn limit_fds() -> libc::rlim_t {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    assert_eq!(
+        unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) },
+        0
+    );
+    // The lowest available file descriptor is used for new files.
+    let next_fd = fs::File::open("/dev/null").unwrap().as_raw_fd();
+    set_fd_limit(next_fd as libc::rlim_t);
+    limit.rlim_cur
+}
+
+/// Sets the soft limit of open file descriptors, see `limit_fds`.
+#[cfg(unix)]
+pub fn set_fd_limit(soft_limit: libc::rlim_t) {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    assert_eq!(
+        unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) },
+        0
+    );
+    limit.rlim_cur = soft_limit;
+    assert_eq!(unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &limit) }, 0);
 }
 
 /// A checked {write, send, send_to} macro that ensures the entire buffer is
@@ -348,6 +392,16 @@ macro_rules! checked_write {
     }};
 }
 
+/// Returns the first `n` bytes of `buf`, as read by one of the `*_uninit`
+/// methods.
+///
+/// # Safety
+///
+/// The first `n` bytes of `buf` must be initialised.
+pub unsafe fn assume_init(buf: &[MaybeUninit<u8>], n: usize) -> &[u8] {
+    &*(&buf[..n] as *const [MaybeUninit<u8>] as *const [u8])
+}
+
 /// A checked {read, recv, recv_from, peek, peek_from} macro that ensures the
 /// current buffer is read.
 ///